@@ -2,22 +2,31 @@
 #![doc(html_logo_url = "https://raw.githubusercontent.com/allay-mc/assets/main/logo-1080x.png")]
 #![doc = include_str!("../README.md")]
 
+pub mod artifacts;
+pub mod cache;
 pub mod config;
 pub mod diagnostic;
 pub mod error;
+pub mod exclude;
 pub mod filter;
 pub mod health;
 pub mod localization;
 pub mod manifest;
+pub mod minify;
+pub mod nbt;
 pub mod pack;
 pub mod paths;
 pub mod plugin;
 pub mod project;
+pub mod report;
 pub mod scaffolding;
+pub mod skins;
+pub mod transpile;
+pub mod typescript;
 pub mod uuid;
 
 pub use config::Config;
-pub use error::Error;
+pub use error::BuildError;
 pub use health::Health;
 pub use manifest::Manifest;
 pub use pack::Pack;
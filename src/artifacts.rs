@@ -0,0 +1,105 @@
+//! History of the artifacts written to the output directory.
+//!
+//! Every build records the add-on and bundles it wrote in `.allay/cache/artifacts.json`. When the
+//! `retain` field of the `[build]` section is set, the artifacts of all but the most recent builds are
+//! removed. Files that were not written by Allay are never touched.
+//!
+//! # Examples
+//!
+//! Below is an example of how an artifacts file could look like (formatted for readability).
+//!
+//! ```json
+//! {
+//!   "builds": [
+//!     ["/home/steve/my-project/dist/My-Project-0.1.0.mcaddon"],
+//!     ["/home/steve/my-project/dist/My-Project-0.2.0.mcaddon"]
+//!   ]
+//! }
+//! ```
+
+use crate::paths;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct History {
+    /// The artifacts of each build starting with the oldest one.
+    pub builds: Vec<Vec<PathBuf>>,
+}
+
+impl History {
+    /// Loads the history of the project located at `root` or returns an empty history if there is none or
+    /// it cannot be read.
+    pub fn load(root: &Path) -> Self {
+        let path = root.join(paths::artifacts());
+        match fs::read_to_string(&path) {
+            Ok(data) => serde_json::from_str(&data).unwrap_or_else(|e| {
+                log::warn!(
+                    "Ignoring invalid artifact history {}: {}",
+                    path.display(),
+                    e
+                );
+                Self::default()
+            }),
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    log::warn!(
+                        "Ignoring unreadable artifact history {}: {}",
+                        path.display(),
+                        e
+                    );
+                }
+                Self::default()
+            }
+        }
+    }
+
+    /// Writes the history to the internal directory of the project located at `root`.
+    pub fn save(&self, root: &Path) -> io::Result<()> {
+        fs::create_dir_all(root.join(paths::cache()))?;
+        fs::write(root.join(paths::artifacts()), serde_json::to_string(self)?)
+    }
+
+    /// Records `artifacts` as the artifacts of the most recent build.
+    ///
+    /// Artifacts that were overwritten are removed from the builds they were recorded for before.
+    pub fn record(&mut self, artifacts: Vec<PathBuf>) {
+        for build in &mut self.builds {
+            build.retain(|artifact| !artifacts.contains(artifact));
+        }
+        self.builds.retain(|build| !build.is_empty());
+        self.builds.push(artifacts);
+    }
+
+    /// Removes the artifacts of all but the `retain` most recent builds.
+    pub fn clean(&mut self, retain: usize) {
+        let outdated = self.builds.len().saturating_sub(retain);
+        for artifact in self.builds.drain(..outdated).flatten() {
+            match fs::remove_file(&artifact) {
+                Ok(_) => log::info!("Removed outdated artifact {}", artifact.display()),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => log::warn!(
+                    "Failed to remove outdated artifact {}: {}",
+                    artifact.display(),
+                    e
+                ),
+            }
+        }
+    }
+
+    /// Records `artifacts` and cleans outdated ones according to `retain` for the project located at
+    /// `root`.
+    pub fn update(root: &Path, artifacts: Vec<PathBuf>, retain: Option<NonZeroUsize>) {
+        let mut history = Self::load(root);
+        history.record(artifacts);
+        if let Some(retain) = retain {
+            history.clean(retain.get());
+        }
+        if let Err(e) = history.save(root) {
+            log::error!("Failed to save artifact history: {}", e);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Persistent build cache used for incremental builds.
+//!
+//! The cache lives in `.allay/cache/` and consists of a `cache.json` file which stores a hash for every
+//! source file of each pack and the bundles produced by the previous build. Packs whose inputs did not
+//! change since the last build are neither copied nor zipped again; their cached bundle is reused instead.
+//!
+//! # Examples
+//!
+//! Below is an example of how a cache file could look like (formatted for readability).
+//!
+//! ```json
+//! {
+//!   "inputs": "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae",
+//!   "packs": {
+//!     "BP": {
+//!       "files": {
+//!         "entities/allay.json": "fcde2b2edba56bf408601fb721fe9b5c338d10ee429ea04fae5511b68fbf8fb9"
+//!       },
+//!       "output": "b5bb9d8014a0f9b1d61e21e796d78dccdf1352f23cd32812f4850b878ae4944c"
+//!     }
+//!   }
+//! }
+//! ```
+
+use crate::{paths, Pack};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Hashes of files keyed by their path relative to the directory they are located in.
+pub type FileHashes = BTreeMap<String, String>;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Cache {
+    /// Hash of the inputs shared by all packs such as the configuration file, the UUIDs, the pack icon and
+    /// the plugins.
+    pub inputs: String,
+
+    /// The state of each pack keyed by its short name (e.g. `BP`).
+    pub packs: HashMap<String, PackState>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct PackState {
+    /// Hashes of the source files of the pack.
+    pub files: FileHashes,
+
+    /// Hash of the pack directory right before it was zipped.
+    pub output: String,
+
+    /// Codes of the notifications raised while preparing the pack.
+    #[serde(default)]
+    pub notifications: Vec<u8>,
+}
+
+impl Cache {
+    /// Loads the cache of the project located at `root` or returns an empty cache if there is none or it
+    /// cannot be read.
+    pub fn load(root: &Path) -> Self {
+        let path = root.join(paths::cache_file());
+        match fs::read_to_string(&path) {
+            Ok(data) => serde_json::from_str(&data).unwrap_or_else(|e| {
+                log::warn!("Ignoring invalid build cache {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    log::warn!("Ignoring unreadable build cache {}: {}", path.display(), e);
+                }
+                Self::default()
+            }
+        }
+    }
+
+    /// Writes the cache to the internal directory of the project located at `root`.
+    pub fn save(&self, root: &Path) -> io::Result<()> {
+        fs::create_dir_all(root.join(paths::cache()))?;
+        fs::write(root.join(paths::cache_file()), serde_json::to_string(self)?)
+    }
+
+    /// Returns `true` when `pack` was built with the same `inputs` and `files` before and its bundle is
+    /// still present.
+    pub fn is_fresh(&self, root: &Path, pack: Pack, inputs: &str, files: &FileHashes) -> bool {
+        self.inputs == inputs
+            && self
+                .packs
+                .get(pack.short_name())
+                .is_some_and(|state| &state.files == files)
+            && bundle(root, pack).is_file()
+    }
+
+    /// Returns `true` when the pack directory hashed to `output` was already zipped to a bundle.
+    pub fn has_output(&self, root: &Path, pack: Pack, output: &str) -> bool {
+        self.packs
+            .get(pack.short_name())
+            .is_some_and(|state| state.output == output)
+            && bundle(root, pack).is_file()
+    }
+}
+
+/// Returns the path of the cached bundle of `pack` for the project located at `root`.
+pub fn bundle(root: &Path, pack: Pack) -> PathBuf {
+    root.join(paths::cache_bundles()).join(format!(
+        "{}.{}",
+        pack.short_name(),
+        pack.bundle_file_extension()
+    ))
+}
+
+/// Returns the SHA-256 hash of `data` as a hexadecimal string.
+pub fn hash(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Returns the SHA-256 hash of the file located at `path`.
+pub fn hash_file(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Hashes every file within `dir` recursively.
+pub fn hash_dir(dir: &Path) -> io::Result<FileHashes> {
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(dir) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            files.push(entry.into_path());
+        }
+    }
+    hash_files(dir, &files)
+}
+
+/// Hashes `files` which are located within `dir`.
+pub fn hash_files(dir: &Path, files: &[PathBuf]) -> io::Result<FileHashes> {
+    let mut hashes = FileHashes::new();
+    for file in files {
+        let relative = file
+            .strip_prefix(dir)
+            .expect("hashed file is within the directory");
+        let key = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        hashes.insert(key, hash_file(file)?);
+    }
+    Ok(hashes)
+}
+
+/// Combines the hashes of several files into one.
+pub fn digest(files: &FileHashes) -> String {
+    let mut hasher = Sha256::new();
+    for (path, hash) in files {
+        hasher.update(path.as_bytes());
+        hasher.update([0]);
+        hasher.update(hash.as_bytes());
+        hasher.update([0]);
+    }
+    format!("{:x}", hasher.finalize())
+}
@@ -1,6 +1,13 @@
 use super::prelude::*;
+use crate::artifacts;
+use crate::config;
+use crate::paths;
 use crate::project::Project;
-use clap::{ArgMatches, Command};
+use crate::report::Report;
+use crate::BuildError;
+use clap::{Arg, ArgMatches, Command};
+use std::fs;
+use std::path::PathBuf;
 use std::process::ExitCode;
 use std::time::Instant;
 
@@ -9,26 +16,121 @@ pub fn cmd() -> Command {
         .visible_alias("b")
         .about("Build the add-ons")
         .arg_build_opts()
+        .arg(
+            Arg::new("report")
+                .long("report")
+                .help("Prints a report of the build in the specified format")
+                .value_name("FORMAT")
+                .value_parser(["json"]),
+        )
+        .arg(
+            Arg::new("report-file")
+                .long("report-file")
+                .help("Writes the report to the specified file instead of the standard output")
+                .value_name("PATH")
+                .value_parser(clap::value_parser!(PathBuf))
+                .requires("report"),
+        )
+}
+
+/// Returns the current project with the build options of `matches` applied.
+pub fn project(matches: &ArgMatches) -> Result<Project, BuildError> {
+    let mut project = Project::current()?;
+    let profile = if let Some(profile) = matches.get_one::<String>("build-profile") {
+        profile.clone()
+    } else if matches.get_flag("build-debug") {
+        config::DEV_PROFILE.to_string()
+    } else if matches.get_flag("build-release") {
+        config::RELEASE_PROFILE.to_string()
+    } else {
+        project.profile.clone()
+    };
+    project.apply_profile(&profile)?;
+    if matches.get_flag("build-no-cache") {
+        project.config.build.incremental = false;
+    }
+    Ok(project)
 }
 
 pub fn run(matches: &ArgMatches) -> ExitCode {
-    let debug_mode: Option<bool> = matches
-        .get_flag("build-debug")
-        .then_some(true)
-        .or(matches.get_flag("build-release").then_some(false));
+    match build(matches) {
+        Some((project, report)) => {
+            artifacts::History::update(
+                &paths::root(),
+                report.addon.into_iter().collect(),
+                project.config.build.retain,
+            );
+            ExitCode::SUCCESS
+        }
+        None => ExitCode::FAILURE,
+    }
+}
+
+/// Builds the current project with the build options of `matches`.
+///
+/// The artifacts are not recorded in the history so that commands building more artifacts can record them
+/// as a single build.
+pub fn build(matches: &ArgMatches) -> Option<(Project, Report)> {
     let now = Instant::now();
-    let mut project = match Project::current() {
+    let mut project = match project(matches) {
         Ok(p) => p,
         Err(e) => {
-            log::error!("{}", e);
-            return ExitCode::FAILURE;
+            e.log();
+            let profile = matches
+                .get_one::<String>("build-profile")
+                .map_or("", String::as_str);
+            let mut report = Report::new(false, profile);
+            report.fail(&e);
+            write_report(matches, &report);
+            return None;
         }
     };
-    if let Some(debug_mode) = debug_mode {
-        project.config.debug = debug_mode;
-    }
-    project.build().expect("failed to build");
+    let mut report = Report::new(project.config.debug, &project.profile);
+    let result = project.build_with_report(&mut report);
     let took = now.elapsed().as_millis();
-    log::info!("Built project in {}ms", took);
-    ExitCode::SUCCESS
+    report.duration_ms = took;
+    match &result {
+        Ok(_) => log::info!("Built project in {}ms", took),
+        Err(e) => {
+            log::error!("Failed to build project");
+            e.log();
+            report.fail(e);
+        }
+    }
+
+    if write_report(matches, &report) && result.is_ok() {
+        Some((project, report))
+    } else {
+        None
+    }
+}
+
+/// Writes `report` if requested with the `--report` option. Returns `false` if it cannot be written.
+fn write_report(matches: &ArgMatches, report: &Report) -> bool {
+    // `report` is only defined for the `build` command itself
+    if matches
+        .try_get_one::<String>("report")
+        .ok()
+        .flatten()
+        .is_none()
+    {
+        return true;
+    }
+    let json = match serde_json::to_string_pretty(report) {
+        Ok(json) => json,
+        Err(e) => {
+            log::error!("Failed to serialize build report: {}", e);
+            return false;
+        }
+    };
+    match matches.get_one::<PathBuf>("report-file") {
+        Some(path) => {
+            if let Err(e) = fs::write(path, json) {
+                log::error!("Failed to write build report to {}: {}", path.display(), e);
+                return false;
+            }
+        }
+        None => println!("{}", json),
+    }
+    true
 }
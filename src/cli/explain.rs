@@ -5,14 +5,18 @@ use std::process::ExitCode;
 pub fn cmd() -> Command {
     Command::new("explain")
         .about("Explain an error or a warning")
-        .arg(Arg::new("id").help("The ID of the diagnostic without the prefix (e.g. `0001`)"))
+        .arg(
+            Arg::new("id")
+                .help("The ID of the diagnostic with or without the prefix (e.g. `W001` or `1`)"),
+        )
 }
 
 pub fn run(matches: &ArgMatches) -> ExitCode {
     let id: &String = matches.get_one("id").unwrap();
     let code: u8 = id
+        .trim_start_matches([diagnostic::ERROR_PREFIX, diagnostic::WARNING_PREFIX])
         .parse()
-        .unwrap_or_else(|_| { panic!("{}", "Code is invalid (must be 0-255)".to_string()) });
+        .unwrap_or_else(|_| panic!("{}", "Code is invalid (must be 0-255)".to_string()));
     let notif = match diagnostic::Notification::from_code(code) {
         Some(n) => n,
         None => {
@@ -12,10 +12,19 @@ pub fn cmd() -> Command {
 }
 
 pub fn run(matches: &ArgMatches) -> ExitCode {
-    build::run(matches);
+    if build::run(matches) != ExitCode::SUCCESS {
+        return ExitCode::FAILURE;
+    }
 
+    let addon = match build::project(matches).and_then(|p| p.artifact(&paths::root(), None)) {
+        Ok(addon) => addon,
+        Err(e) => {
+            e.log();
+            return ExitCode::FAILURE;
+        }
+    };
     log::info!("Exporting add-on...");
-    match open::that(paths::root().join(paths::build())) {
+    match open::that(addon) {
         Ok(_) => ExitCode::SUCCESS,
         Err(e) => {
             log::error!("Could not export add-on: {}", e);
@@ -9,12 +9,14 @@ mod init;
 mod logs;
 #[cfg(feature = "manual")]
 mod manual;
+mod pack;
 mod prelude;
 #[cfg(feature = "config-schema")]
 mod schema;
 #[cfg(feature = "share")]
 mod share;
 mod sync;
+mod unsync;
 mod uuid;
 #[cfg(feature = "watch")]
 mod watch;
@@ -25,6 +27,7 @@ use log::{Level, LevelFilter};
 use simplelog::{
     Color, ColorChoice, CombinedLogger, SharedLogger, TermLogger, TerminalMode, WriteLogger,
 };
+use std::path::PathBuf;
 use std::process::ExitCode;
 use std::{fs::File, panic};
 use textwrap_macros::dedent;
@@ -88,11 +91,13 @@ pub fn cmd() -> Command {
             logs::cmd(),
             #[cfg(feature = "manual")]
             manual::cmd(),
+            pack::cmd(),
             #[cfg(feature = "config-schema")]
             schema::cmd(),
             #[cfg(feature = "share")]
             share::cmd(),
             sync::cmd(),
+            unsync::cmd(),
             uuid::cmd(),
             #[cfg(feature = "watch")]
             watch::cmd(),
@@ -144,11 +149,20 @@ pub fn run(matches: &ArgMatches) -> ExitCode {
             .unwrap_or_else(|old| old)
             .build();
 
+        // keep the standard output clean when it is used for the build report
+        let report_to_stdout = matches.subcommand_matches("build").is_some_and(|m| {
+            m.get_one::<String>("report").is_some() && m.get_one::<PathBuf>("report-file").is_none()
+        });
+
         if !matches.get_flag("quiet") {
             loggers.push(TermLogger::new(
                 level,
                 config_term,
-                TerminalMode::Mixed,
+                if report_to_stdout {
+                    TerminalMode::Stderr
+                } else {
+                    TerminalMode::Mixed
+                },
                 ColorChoice::Auto,
             ));
         }
@@ -174,11 +188,13 @@ pub fn run(matches: &ArgMatches) -> ExitCode {
         Some(("logs", m)) => logs::run(m),
         #[cfg(feature = "manual")]
         Some(("manual", m)) => manual::run(m),
+        Some(("pack", m)) => pack::run(m),
         #[cfg(feature = "config-schema")]
         Some(("schema", m)) => schema::run(m),
         #[cfg(feature = "share")]
         Some(("share", m)) => share::run(m),
         Some(("sync", m)) => sync::run(m),
+        Some(("unsync", m)) => unsync::run(m),
         Some(("uuid", m)) => uuid::run(m),
         #[cfg(feature = "watch")]
         Some(("watch", m)) => watch::run(m),
@@ -0,0 +1,97 @@
+use super::build;
+use super::prelude::*;
+use crate::artifacts;
+use crate::cache;
+use crate::paths;
+use crate::Pack;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+pub fn cmd() -> Command {
+    Command::new("pack")
+        .about("Build the add-ons and write the bundle of each pack into a directory")
+        .long_about(
+            "Build the add-ons and write the bundle of each pack (e.g. `.mcpack` and `.mctemplate` files) \
+             into a directory which is the output directory configured in the `[build]` section by \
+             default",
+        )
+        .arg(
+            Arg::new("out-dir")
+                .short('o')
+                .long("out-dir")
+                .help("Directory to write the bundles to")
+                .value_name("DIR")
+                .value_parser(clap::value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("addon")
+                .long("addon")
+                .help("Also write the add-on containing every pack")
+                .action(ArgAction::SetTrue),
+        )
+        .arg_build_opts()
+}
+
+pub fn run(matches: &ArgMatches) -> ExitCode {
+    let Some((project, report)) = build::build(matches) else {
+        return ExitCode::FAILURE;
+    };
+    let root = paths::root();
+    let out_dir = match matches.get_one::<PathBuf>("out-dir") {
+        Some(dir) => dir.clone(),
+        None => root.join(&project.config.build.output),
+    };
+    if let Err(e) = fs::create_dir_all(&out_dir) {
+        log::error!("Failed to create {}: {}", out_dir.display(), e);
+        return ExitCode::FAILURE;
+    }
+
+    let mut packs: Vec<Option<Pack>> = Pack::VALUES
+        .into_iter()
+        .filter(Pack::exists)
+        .map(Some)
+        .collect();
+    if matches.get_flag("addon") {
+        packs.push(None);
+    }
+    // the add-on and the bundles are recorded as a single build
+    let mut artifacts: Vec<PathBuf> = report.addon.into_iter().collect();
+    for pack in packs {
+        let (from, name) = match pack {
+            Some(pack) => (
+                Ok(cache::bundle(&root, pack)),
+                project.artifact_name(Some(pack)),
+            ),
+            None => (project.artifact(&root, None), project.artifact_name(None)),
+        };
+        let (from, to) = match from.and_then(|from| Ok((from, out_dir.join(name?)))) {
+            Ok(paths) => paths,
+            Err(e) => {
+                e.log();
+                return ExitCode::FAILURE;
+            }
+        };
+        // the add-on is already located in the output directory unless `--out-dir` is passed
+        let same_file = fs::canonicalize(&from)
+            .is_ok_and(|from| fs::canonicalize(&to).is_ok_and(|to| from == to));
+        if !same_file {
+            if let Err(e) = fs::copy(&from, &to) {
+                log::error!(
+                    "Failed to copy {} to {}: {}",
+                    from.display(),
+                    to.display(),
+                    e
+                );
+                return ExitCode::FAILURE;
+            }
+        }
+        log::info!("Wrote {}", to.display());
+        if !artifacts.contains(&to) {
+            artifacts.push(to);
+        }
+    }
+    artifacts::History::update(&root, artifacts, project.config.build.retain);
+    ExitCode::SUCCESS
+}
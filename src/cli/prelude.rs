@@ -8,9 +8,9 @@ pub trait CommandExt: Sized {
             Arg::new("build-release")
                 .short('r')
                 .long("release")
-                .help("Builds the project in release mode")
+                .help("Builds the project in release mode using the `release` profile")
                 .action(ArgAction::SetTrue)
-                .conflicts_with("build-debug"),
+                .conflicts_with_all(["build-debug", "build-profile"]),
         )
     }
 
@@ -19,18 +19,38 @@ pub trait CommandExt: Sized {
             Arg::new("build-debug")
                 .short('d')
                 .long("debug")
-                .help("Builds the project in debug mode")
+                .help("Builds the project in debug mode using the `dev` profile")
                 .action(ArgAction::SetTrue)
-                .conflicts_with("build-release"),
+                .conflicts_with_all(["build-release", "build-profile"]),
+        )
+    }
+
+    fn arg_profile(self) -> Self {
+        self._arg(
+            Arg::new("build-profile")
+                .long("profile")
+                .help("Builds the project using the specified profile")
+                .value_name("NAME"),
         )
     }
 
     fn arg_build_mode(self) -> Self {
-        self.arg_debug().arg_release()
+        self.arg_debug().arg_release().arg_profile()
+    }
+
+    fn arg_no_cache(self) -> Self {
+        self._arg(
+            Arg::new("build-no-cache")
+                .long("no-cache")
+                .help(
+                    "Rebuilds every pack instead of reusing unchanged ones from the previous build",
+                )
+                .action(ArgAction::SetTrue),
+        )
     }
 
     fn arg_build_opts(self) -> Self {
-        self.arg_build_mode()
+        self.arg_build_mode().arg_no_cache()
     }
 }
 
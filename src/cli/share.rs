@@ -32,7 +32,19 @@ pub fn cmd() -> Command {
 
 #[tokio::main(flavor = "current_thread")]
 pub async fn run(matches: &ArgMatches) -> ExitCode {
-    build::run(matches);
+    if build::run(matches) != ExitCode::SUCCESS {
+        return ExitCode::FAILURE;
+    }
+
+    let (addon, file_name) = match build::project(matches)
+        .and_then(|p| Ok((p.artifact(&paths::root(), None)?, p.artifact_name(None)?)))
+    {
+        Ok(artifact) => artifact,
+        Err(e) => {
+            e.log();
+            return ExitCode::FAILURE;
+        }
+    };
 
     let host: Option<&String> = matches.get_one("host");
     let host: IpAddr = match host {
@@ -54,13 +66,12 @@ pub async fn run(matches: &ArgMatches) -> ExitCode {
         }
         Err(e) => log::error!("Error while trying to generate QR code: {}", e),
     };
-    let app =
-        warp::path::end()
-            .and(warp::fs::file(paths::build()))
-            .with(warp::reply::with::header(
-                "Content-Disposition",
-                r#"attachment; filename="build.mcaddon""#, // TODO: use project name?
-            ));
+    let app = warp::path::end()
+        .and(warp::fs::file(addon))
+        .with(warp::reply::with::header(
+            "Content-Disposition",
+            format!(r#"attachment; filename="{}""#, file_name),
+        ));
     warp::serve(app).run((host, *port)).await;
     ExitCode::SUCCESS
 }
@@ -0,0 +1,84 @@
+//! Installation of packs into a [Bedrock Dedicated Server][bds].
+//!
+//! The packs are installed into the `development_behavior_packs` and `development_resource_packs` directories
+//! of the server and applied to the world named by `level-name` in `server.properties`.
+//!
+//! [bds]: https://www.minecraft.net/en-us/download/server/bedrock
+
+use crate::nbt::{Compound, LevelDat, Tag};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The name of the file containing the configuration of the server.
+const SERVER_PROPERTIES: &str = "server.properties";
+
+/// The name of the world the server loads when `level-name` is not set.
+const DEFAULT_LEVEL_NAME: &str = "Bedrock level";
+
+/// Flags in the `experiments` tag of `level.dat` set whenever an experiment is enabled.
+const EXPERIMENTS_USED: [&str; 2] = ["experiments_ever_used", "saved_with_toggled_experiments"];
+
+/// Returns the directory of the world loaded by the server located at `root`.
+pub fn world(root: &Path) -> io::Result<PathBuf> {
+    let path = root.join(SERVER_PROPERTIES);
+    let properties = fs::read_to_string(&path)
+        .map_err(|e| io::Error::new(e.kind(), format!("cannot read {}: {}", path.display(), e)))?;
+    let level_name = properties
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "level-name")
+        .map(|(_, value)| value.trim())
+        .filter(|value| !value.is_empty())
+        .unwrap_or(DEFAULT_LEVEL_NAME);
+    Ok(root.join("worlds").join(level_name))
+}
+
+/// Parses an experiment passed as `NAME` or `NAME=BOOL`.
+pub fn parse_experiment(s: &str) -> Result<(String, bool), String> {
+    let (name, enabled) = match s.split_once('=') {
+        Some((name, value)) => (
+            name,
+            value
+                .parse()
+                .map_err(|_| format!("expected `true` or `false`, found `{}`", value))?,
+        ),
+        None => (s, true),
+    };
+    if name.is_empty() {
+        return Err("missing name of experiment".to_string());
+    }
+    Ok((name.to_string(), enabled))
+}
+
+/// Enables or disables `experiments` in the `level.dat` file of the world located at `world`.
+///
+/// Returns `true` if the file was changed.
+pub fn set_experiments(world: &Path, experiments: &[(String, bool)]) -> io::Result<bool> {
+    let path = world.join("level.dat");
+    let mut level = LevelDat::load(&path)?;
+    let original = level.root.clone();
+    if !matches!(level.root.get("experiments"), Some(Tag::Compound(_))) {
+        level
+            .root
+            .insert("experiments", Tag::Compound(Compound::default()));
+    }
+    let Some(Tag::Compound(tags)) = level.root.get_mut("experiments") else {
+        unreachable!("experiments tag was inserted above");
+    };
+    for (name, enabled) in experiments {
+        tags.insert(name.as_str(), Tag::Byte(i8::from(*enabled)));
+    }
+    if experiments.iter().any(|(_, enabled)| *enabled) {
+        for flag in EXPERIMENTS_USED {
+            tags.insert(flag, Tag::Byte(1));
+        }
+    }
+    if level.root == original {
+        return Ok(false);
+    }
+    level.save(&path)?;
+    Ok(true)
+}
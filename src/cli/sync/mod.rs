@@ -0,0 +1,442 @@
+use super::build;
+use super::prelude::*;
+use crate::{cache, paths, Pack, Project};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use libuuid::Uuid;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::{self, FromStr};
+use std::{env, path::PathBuf, process::ExitCode};
+use target::Target;
+use zip_extensions::read::zip_extract;
+
+mod bds;
+pub mod target;
+pub mod world;
+
+const DEV_BP: &str = "development_behavior_packs";
+const DEV_RP: &str = "development_resource_packs";
+const DEV_SP: &str = "development_skin_packs";
+
+/// Returns the development directory `pack` is installed to or [`None`] if the pack cannot be synced.
+pub fn dev_dir(pack: Pack) -> Option<&'static str> {
+    match pack {
+        Pack::Behavior => Some(DEV_BP),
+        Pack::Resource => Some(DEV_RP),
+        Pack::Skin => Some(DEV_SP),
+        Pack::WorldTemplate => None,
+    }
+}
+
+pub mod location {
+    use crate::diagnostic::Notification;
+    use std::{env, path::PathBuf};
+
+    fn android_termux() -> PathBuf {
+        PathBuf::from(env::var_os("HOME").expect("HOME environment variable not set"))
+            .join("storage/shared/Android/data/com.mojang.minecraftpe/files/games/com.mojang")
+    }
+
+    fn windows() -> PathBuf {
+        PathBuf::from(
+            env::var_os("LOCALAPPDATA").expect("LOCALAPPDATA environment variable not set"),
+        )
+        .join("Packages/Microsoft.MinecraftUWP_8wekyb3d8bbwe/LocalState/games/com.mojang")
+    }
+
+    fn linux() -> PathBuf {
+        PathBuf::from(env::var_os("HOME").expect("HOME environment variable not set"))
+            .join(".local/share/mcpelauncher/games/com.mojang")
+    }
+
+    /// Returns the location of the `com.mojang` directory on this OS and the notification raised when it
+    /// does not exist.
+    fn expected() -> Result<(PathBuf, Notification), &'static str> {
+        if cfg!(target_os = "android") {
+            Ok((android_termux(), Notification::ComMojangNotFoundAndroid))
+        } else if cfg!(target_os = "ios") {
+            Err("iOS devices are not supported for syncing yet")
+        } else if cfg!(target_os = "windows") {
+            Ok((windows(), Notification::ComMojangNotFoundWindows))
+        } else if cfg!(target_os = "linux") {
+            Ok((linux(), Notification::ComMojangNotFoundLinux))
+        } else {
+            Err("Your OS is not supported for syncing")
+        }
+    }
+
+    /// Returns the `com.mojang` directory or logs an error if it cannot be found.
+    pub fn get() -> Option<PathBuf> {
+        match expected() {
+            Ok((path, _)) if path.exists() => Some(path),
+            Ok((_, notification)) => {
+                log::error!("{}", notification);
+                None
+            }
+            Err(message) => {
+                log::error!("{}", message);
+                None
+            }
+        }
+    }
+
+    /// Returns the `com.mojang` directory set with the `COM_MOJANG` environment variable or found at its
+    /// usual location without logging an error.
+    pub fn find() -> Option<PathBuf> {
+        match env::var_os("COM_MOJANG") {
+            Some(var) => Some(PathBuf::from(var)),
+            None => expected()
+                .ok()
+                .map(|(path, _)| path)
+                .filter(|path| path.exists()),
+        }
+    }
+}
+
+/// Returns the directories within `dev_dir` containing a pack with the fingerprint `project_id`.
+pub fn installed(dev_dir: &Path, project_id: &Uuid) -> io::Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    if !dev_dir.is_dir() {
+        return Ok(dirs);
+    }
+    for entry in dev_dir.read_dir()? {
+        let path = entry?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let fingerprint = path.join(paths::FINGERPRINT);
+        match fs::read(&fingerprint) {
+            Ok(value) => {
+                // the fingerprint is written as raw bytes but may have been edited by hand
+                let id = Uuid::from_slice(&value).ok().or_else(|| {
+                    str::from_utf8(&value)
+                        .ok()
+                        .and_then(|value| Uuid::from_str(value.trim()).ok())
+                });
+                if id.as_ref() == Some(project_id) {
+                    dirs.push(path);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => log::warn!("Failed to read {}: {}", fingerprint.display(), e),
+        }
+    }
+    Ok(dirs)
+}
+
+/// Extracts `bundle` into the directory `name` within `dev_dir` and removes the other directories of the
+/// project with the ID `project_id` except for those in `keep`.
+///
+/// Returns the path of the written directory.
+fn install(
+    bundle: &Path,
+    dev_dir: &Path,
+    name: &str,
+    project_id: &Uuid,
+    keep: &[PathBuf],
+) -> io::Result<PathBuf> {
+    let dest = dev_dir.join(name);
+    let mut previous = installed(dev_dir, project_id)?;
+    if dest.exists() && !previous.contains(&dest) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!(
+                "{} already exists and does not belong to this project",
+                dest.display()
+            ),
+        ));
+    }
+    previous.retain(|dir| !keep.contains(dir));
+    for dir in &previous {
+        log::debug!("Removing {}", dir.display());
+        fs::remove_dir_all(dir)?;
+    }
+    fs::create_dir_all(&dest)?;
+    zip_extract(&bundle.to_path_buf(), &dest).map_err(io::Error::other)?;
+    Ok(dest)
+}
+
+pub fn cmd() -> Command {
+    Command::new("sync")
+        .about("Install the packs into the development directories of Minecraft")
+        .long_about(
+            "Build the add-ons and install the behavior pack, the resource pack and the skin pack into \
+             the `development_*_packs` directories of the `com.mojang` directory which can be set with the \
+             `COM_MOJANG` environment variable or into the targets defined in `[[sync.target]]` sections \
+             and in the user-level `sync.toml` file. Directories of previously synced packs of the project \
+             are replaced",
+        )
+        .arg(
+            Arg::new("targets")
+                .help("Names of the targets to install the packs into")
+                .value_name("TARGET")
+                .num_args(0..)
+                .conflicts_with_all(["world", "bds"]),
+        )
+        .arg(
+            Arg::new("all")
+                .long("all")
+                .help("Install the packs into every defined target")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["targets", "world", "bds"]),
+        )
+        .arg(
+            Arg::new("world")
+                .long("world")
+                .help("Install the behavior pack and the resource pack into a world and apply them")
+                .long_help(
+                    "Install the behavior pack and the resource pack into the world with this name or \
+                     folder in `com.mojang/minecraftWorlds` and apply them to the world instead of \
+                     installing them into the development directories",
+                )
+                .value_name("WORLD")
+                .conflicts_with("bds"),
+        )
+        .arg(
+            Arg::new("bds")
+                .long("bds")
+                .help("Install the behavior pack and the resource pack into a Bedrock Dedicated Server")
+                .long_help(
+                    "Install the behavior pack and the resource pack into the development directories of \
+                     the Bedrock Dedicated Server located at this directory and apply them to the world \
+                     named by `level-name` in its `server.properties` file",
+                )
+                .value_name("DIR")
+                .value_parser(clap::value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("experiment")
+                .long("experiment")
+                .help("Enable or disable an experiment in the world of the server")
+                .long_help(
+                    "Enable (`NAME` or `NAME=true`) or disable (`NAME=false`) an experiment such as \
+                     `gametest` in the `level.dat` file of the world of the Bedrock Dedicated Server",
+                )
+                .value_name("NAME[=BOOL]")
+                .value_parser(bds::parse_experiment)
+                .action(ArgAction::Append)
+                .requires("bds"),
+        )
+        .arg_build_opts()
+}
+
+/// A target selected to install the packs into.
+struct Destination {
+    /// The name of the target or its path if it is not defined in the configuration.
+    name: String,
+
+    target: Target,
+
+    /// The packs to install or [`None`] to install every pack.
+    packs: Option<Vec<Pack>>,
+}
+
+/// Returns the targets selected by the options in `matches` or [`None`] if a target cannot be found.
+fn targets(matches: &ArgMatches, project: &Project) -> Option<Vec<Destination>> {
+    let names: Vec<&String> = matches
+        .get_many::<String>("targets")
+        .unwrap_or_default()
+        .collect();
+    let all = matches.get_flag("all");
+    if all || !names.is_empty() {
+        let configured = match target::configured(project) {
+            Ok(targets) => targets,
+            Err(e) => {
+                e.log();
+                return None;
+            }
+        };
+        if all && configured.is_empty() {
+            log::error!("No targets are defined in `[[sync.target]]` sections or the user-level `sync.toml` file");
+            return None;
+        }
+        let selected: Vec<_> = if all {
+            configured.iter().collect()
+        } else {
+            let mut selected = Vec::new();
+            for name in names {
+                match configured.iter().find(|target| &target.name == name) {
+                    Some(target) => selected.push(target),
+                    None => {
+                        log::error!(
+                            "Unknown target `{}`; available targets: {}",
+                            name,
+                            configured
+                                .iter()
+                                .map(|target| format!("`{}`", target.name))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        );
+                        return None;
+                    }
+                }
+            }
+            selected
+        };
+        let root = paths::root();
+        let mut targets = Vec::new();
+        for config in selected {
+            match Target::new(&root, config) {
+                Ok(target) => targets.push(Destination {
+                    name: config.name.clone(),
+                    target,
+                    packs: config.packs.clone(),
+                }),
+                Err(e) => {
+                    log::error!("Failed to find target `{}`: {}", config.name, e);
+                    return None;
+                }
+            }
+        }
+        return Some(targets);
+    }
+
+    if let Some(root) = matches.get_one::<PathBuf>("bds") {
+        let experiments = matches
+            .get_many::<(String, bool)>("experiment")
+            .unwrap_or_default()
+            .cloned()
+            .collect();
+        return match bds::world(root) {
+            Ok(world) => Some(vec![Destination {
+                name: root.display().to_string(),
+                target: Target::Bds {
+                    root: root.clone(),
+                    world,
+                    experiments,
+                },
+                packs: None,
+            }]),
+            Err(e) => {
+                log::error!("Failed to find world of server: {}", e);
+                None
+            }
+        };
+    }
+
+    let com_mojang: PathBuf = match env::var_os("COM_MOJANG") {
+        Some(var) => PathBuf::from(var),
+        None => location::get()?,
+    };
+    let target = match matches.get_one::<String>("world") {
+        Some(name) => match world::find(&com_mojang.join(world::WORLDS), name) {
+            Ok(world) => Target::World(world),
+            Err(e) => {
+                log::error!("Failed to find world: {}", e);
+                return None;
+            }
+        },
+        None => Target::ComMojang(com_mojang.clone()),
+    };
+    Some(vec![Destination {
+        name: com_mojang.display().to_string(),
+        target,
+        packs: None,
+    }])
+}
+
+/// Installs the packs of `project` into `target`. Only `packs` are installed when given.
+///
+/// Returns `false` if any pack could not be installed.
+fn sync(project: &Project, target: &Target, packs: Option<&[Pack]>) -> bool {
+    let root = paths::root();
+    let mut success = true;
+    let mut written = Vec::new();
+    for pack in Pack::VALUES.into_iter().filter(Pack::exists) {
+        if packs.is_some_and(|packs| !packs.contains(&pack)) {
+            continue;
+        }
+        let Some(dir) = target.pack_dir(pack) else {
+            continue;
+        };
+        let name = match project.artifact_name(Some(pack)) {
+            Ok(name) => name,
+            Err(e) => {
+                e.log();
+                return false;
+            }
+        };
+        // the directory is named after the bundle without its extension
+        let name = Path::new(&name)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or(name);
+        let bundle = cache::bundle(&root, pack);
+        let dest = match install(&bundle, &dir, &name, &project.id, &written) {
+            Ok(dest) => dest,
+            Err(e) => {
+                log::error!("Failed to sync {}: {}", pack, e);
+                success = false;
+                continue;
+            }
+        };
+        log::info!("Synced {} to {}", pack, dest.display());
+        written.push(dest.clone());
+
+        if let Some(world) = target.world() {
+            let Some(id) = project.uuids.of(&pack).header else {
+                continue;
+            };
+            let registered = world::version(&dest).and_then(|version| {
+                fs::create_dir_all(world)?;
+                world::register(world, pack, id, version)
+            });
+            match registered {
+                Ok(true) => log::info!("Applied {} to {}", pack, world.display()),
+                Ok(false) => log::debug!("{} is already applied to {}", pack, world.display()),
+                Err(e) => {
+                    log::error!("Failed to apply {} to {}: {}", pack, world.display(), e);
+                    success = false;
+                }
+            }
+        }
+    }
+
+    if let Target::Bds {
+        world, experiments, ..
+    } = target
+    {
+        if !experiments.is_empty() {
+            match bds::set_experiments(world, experiments) {
+                Ok(true) => log::info!("Updated experiments of {}", world.display()),
+                Ok(false) => log::debug!("Experiments of {} are up to date", world.display()),
+                Err(e) => {
+                    log::error!("Failed to update experiments of {}: {}", world.display(), e);
+                    success = false;
+                }
+            }
+        }
+    }
+    success
+}
+
+pub fn run(matches: &ArgMatches) -> ExitCode {
+    if build::run(matches) != ExitCode::SUCCESS {
+        return ExitCode::FAILURE;
+    }
+    let project = match build::project(matches) {
+        Ok(project) => project,
+        Err(e) => {
+            e.log();
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(targets) = targets(matches, &project) else {
+        return ExitCode::FAILURE;
+    };
+
+    let mut success = true;
+    for destination in &targets {
+        log::info!("Syncing to {}", destination.name);
+        if !sync(&project, &destination.target, destination.packs.as_deref()) {
+            success = false;
+        }
+    }
+
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,154 @@
+//! Locations the packs are installed to.
+//!
+//! Besides the `com.mojang` directory found by default, targets can be defined in `[[sync.target]]` sections
+//! of the configuration file and in the user-level `sync.toml` file which uses the same format without the
+//! `sync.` prefix:
+//!
+//! ```toml
+//! [[target]]
+//! name = "server"
+//! type = "bds"
+//! path = "/home/steve/bedrock-server"
+//! ```
+//!
+//! Relative paths in `sync.toml` are resolved against the directory containing it.
+
+use super::{bds, dev_dir, world};
+use crate::config::{self, SyncTarget, SyncTargetKind};
+use crate::{paths, BuildError, Pack, Project};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The location the packs are installed to.
+#[derive(Clone, Debug)]
+pub enum Target {
+    /// The `development_*_packs` directories of a `com.mojang` directory.
+    ComMojang(PathBuf),
+
+    /// A world within the `minecraftWorlds` directory of a `com.mojang` directory.
+    World(PathBuf),
+
+    /// A Bedrock Dedicated Server located at `root` which loads the world located at `world` whose
+    /// `experiments` are enabled or disabled.
+    Bds {
+        root: PathBuf,
+        world: PathBuf,
+        experiments: Vec<(String, bool)>,
+    },
+
+    /// A directory the packs are extracted into.
+    Directory(PathBuf),
+}
+
+impl Target {
+    /// Creates the target defined by `config` for the project located at `root`.
+    pub fn new(root: &Path, config: &SyncTarget) -> io::Result<Self> {
+        let path = root.join(&config.path);
+        Ok(match (config.kind, &config.world) {
+            (SyncTargetKind::ComMojang, Some(name)) => {
+                Self::World(world::find(&path.join(world::WORLDS), name)?)
+            }
+            (SyncTargetKind::ComMojang, None) => Self::ComMojang(path),
+            (SyncTargetKind::Bds, _) => Self::Bds {
+                world: bds::world(&path)?,
+                root: path,
+                experiments: config
+                    .experiments
+                    .iter()
+                    .map(|(name, enabled)| (name.clone(), *enabled))
+                    .collect(),
+            },
+            (SyncTargetKind::Directory, _) => Self::Directory(path),
+        })
+    }
+
+    /// Returns the directory `pack` is installed to or [`None`] if the pack cannot be installed to the
+    /// target.
+    pub fn pack_dir(&self, pack: Pack) -> Option<PathBuf> {
+        match self {
+            Self::ComMojang(com_mojang) => dev_dir(pack).map(|dir| com_mojang.join(dir)),
+            Self::World(world) => world::pack_dir(pack).map(|dir| world.join(dir)),
+            // skin packs are not used by servers
+            Self::Bds { root, .. } => world::pack_dir(pack)
+                .and(dev_dir(pack))
+                .map(|dir| root.join(dir)),
+            Self::Directory(dir) => Some(dir.clone()),
+        }
+    }
+
+    /// Returns the world the packs are applied to.
+    pub fn world(&self) -> Option<&Path> {
+        match self {
+            Self::ComMojang(_) | Self::Directory(_) => None,
+            Self::World(world) | Self::Bds { world, .. } => Some(world),
+        }
+    }
+
+    /// Returns every world of the target including those the packs were not applied to by `sync`.
+    pub fn worlds(&self) -> Vec<PathBuf> {
+        let dir = match self {
+            Self::ComMojang(com_mojang) => com_mojang.join(world::WORLDS),
+            Self::Bds { root, .. } => root.join("worlds"),
+            Self::World(world) => return vec![world.clone()],
+            Self::Directory(_) => return Vec::new(),
+        };
+        let Ok(entries) = dir.read_dir() else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect()
+    }
+
+    /// Returns every directory of the target which may contain packs.
+    pub fn pack_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = Pack::VALUES
+            .into_iter()
+            .filter_map(|pack| self.pack_dir(pack))
+            .collect();
+        for world in self.worlds() {
+            dirs.extend(
+                Pack::VALUES
+                    .into_iter()
+                    .filter_map(world::pack_dir)
+                    .map(|dir| world.join(dir)),
+            );
+        }
+        dirs.sort();
+        dirs.dedup();
+        dirs
+    }
+}
+
+/// Returns the user-level targets stored in Allay's configuration directory.
+fn user_targets() -> Result<Vec<SyncTarget>, BuildError> {
+    let path = paths::sync_targets();
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(BuildError::io(&path)(e)),
+    };
+    let mut targets = config::Sync::from_str(&content)
+        .map(|sync| sync.targets)
+        .map_err(|e| BuildError::config(&path, &content, e.span(), e.message()))?;
+    // the paths are joined with the project root later on which keeps absolute paths
+    let dir = path.parent().unwrap_or(Path::new(""));
+    for target in &mut targets {
+        target.path = dir.join(&target.path);
+    }
+    Ok(targets)
+}
+
+/// Returns the targets of `project` and the user-level targets. User-level targets replace targets of the
+/// project with the same name.
+pub fn configured(project: &Project) -> Result<Vec<SyncTarget>, BuildError> {
+    let mut targets = project.config.sync.targets.clone();
+    for target in user_targets()? {
+        targets.retain(|t| t.name != target.name);
+        targets.push(target);
+    }
+    Ok(targets)
+}
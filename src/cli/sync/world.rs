@@ -0,0 +1,147 @@
+//! Installation of packs into worlds.
+//!
+//! Packs are applied to a world by listing them in the `world_behavior_packs.json` and
+//! `world_resource_packs.json` files of the world directory:
+//!
+//! ```json
+//! [
+//!   { "pack_id": "2c6a4ed0-5d4e-4c1b-9d5d-3c6b1c0e4f3a", "version": [0, 1, 0] }
+//! ]
+//! ```
+
+use crate::Pack;
+use libuuid::Uuid;
+use serde_json::{json, Value};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The name of the directory within `com.mojang` containing the worlds.
+pub const WORLDS: &str = "minecraftWorlds";
+
+/// The name of the file containing the name of a world shown in the game.
+const LEVEL_NAME: &str = "levelname.txt";
+
+/// Returns the directory within a world `pack` is installed to or [`None`] if the pack cannot be applied to
+/// worlds.
+pub fn pack_dir(pack: Pack) -> Option<&'static str> {
+    match pack {
+        Pack::Behavior => Some("behavior_packs"),
+        Pack::Resource => Some("resource_packs"),
+        Pack::Skin | Pack::WorldTemplate => None,
+    }
+}
+
+/// Returns the files within the world located at `world` listing the packs with one of the UUIDs in `ids`.
+pub fn referencing(world: &Path, ids: &[Uuid]) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for file in Pack::VALUES.into_iter().filter_map(packs_file) {
+        let path = world.join(file);
+        let entries = match read_entries(&path) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        if entries.iter().any(|entry| references(entry, ids)) {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Removes the entries of the packs with one of the UUIDs in `ids` from the file located at `path`.
+pub fn unregister(path: &Path, ids: &[Uuid]) -> io::Result<()> {
+    let mut entries = read_entries(path)?;
+    entries.retain(|entry| !references(entry, ids));
+    fs::write(path, serde_json::to_string_pretty(&entries)?)
+}
+
+fn read_entries(path: &Path) -> io::Result<Vec<Value>> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// Returns `true` if `entry` lists a pack with one of the UUIDs in `ids`.
+fn references(entry: &Value, ids: &[Uuid]) -> bool {
+    entry
+        .get("pack_id")
+        .and_then(Value::as_str)
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .is_some_and(|id| ids.contains(&id))
+}
+
+/// Returns the file within a world listing the applied packs of the same type as `pack`.
+fn packs_file(pack: Pack) -> Option<&'static str> {
+    match pack {
+        Pack::Behavior => Some("world_behavior_packs.json"),
+        Pack::Resource => Some("world_resource_packs.json"),
+        Pack::Skin | Pack::WorldTemplate => None,
+    }
+}
+
+/// Finds the world within `worlds` whose directory or name in `levelname.txt` is `name`.
+pub fn find(worlds: &Path, name: &str) -> io::Result<PathBuf> {
+    let folder = worlds.join(name);
+    if folder.join(LEVEL_NAME).is_file() {
+        return Ok(folder);
+    }
+    let mut found = Vec::new();
+    for entry in worlds.read_dir()? {
+        let path = entry?.path();
+        match fs::read_to_string(path.join(LEVEL_NAME)) {
+            Ok(level_name) if level_name.trim() == name => found.push(path),
+            _ => {}
+        }
+    }
+    match found.len() {
+        0 => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no world named `{}` found in {}", name, worlds.display()),
+        )),
+        1 => Ok(found.remove(0)),
+        _ => Err(io::Error::other(format!(
+            "multiple worlds are named `{}`; use one of the folders instead: {}",
+            name,
+            found
+                .iter()
+                .filter_map(|path| path.file_name())
+                .map(|name| name.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+/// Returns the version in the manifest of the pack located at `dir`.
+pub fn version(dir: &Path) -> io::Result<Value> {
+    let manifest: Value = serde_json::from_str(&fs::read_to_string(dir.join("manifest.json"))?)?;
+    manifest
+        .pointer("/header/version")
+        .cloned()
+        .ok_or_else(|| io::Error::other("manifest does not contain a version"))
+}
+
+/// Applies `pack` with the UUID `id` and `version` to the world located at `world`.
+///
+/// An existing entry of the pack is updated. Returns `true` if the file listing the packs was changed.
+pub fn register(world: &Path, pack: Pack, id: Uuid, version: Value) -> io::Result<bool> {
+    let Some(file) = packs_file(pack) else {
+        return Ok(false);
+    };
+    let path = world.join(file);
+    let mut entries = match read_entries(&path) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e),
+    };
+    let entry = json!({ "pack_id": id.to_string(), "version": version });
+    let existing = entries
+        .iter_mut()
+        .find(|e| e.get("pack_id").and_then(Value::as_str) == Some(&id.to_string()));
+    match existing {
+        Some(existing) if *existing == entry => return Ok(false),
+        Some(existing) => *existing = entry,
+        None => entries.push(entry),
+    }
+    fs::write(&path, serde_json::to_string_pretty(&entries)?)?;
+    Ok(true)
+}
@@ -0,0 +1,137 @@
+use super::sync::{self, location, target, world};
+use crate::{paths, BuildError, Pack, Project};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use libuuid::Uuid;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+pub fn cmd() -> Command {
+    Command::new("unsync")
+        .about("Remove the packs installed by the `sync` command")
+        .long_about(
+            "Remove the directories of the project's packs from the `com.mojang` directory and every \
+             target defined in `[[sync.target]]` sections or the user-level `sync.toml` file and remove the \
+             packs from the worlds they are applied to",
+        )
+        .arg(
+            Arg::new("yes")
+                .short('y')
+                .long("yes")
+                .help("Remove the packs without asking for confirmation")
+                .action(ArgAction::SetTrue),
+        )
+}
+
+/// Returns the targets the packs may have been installed to.
+fn targets(project: &Project) -> Result<Vec<target::Target>, BuildError> {
+    let mut targets = Vec::new();
+    if let Some(com_mojang) = location::find() {
+        targets.push(target::Target::ComMojang(com_mojang));
+    }
+    let root = paths::root();
+    for config in target::configured(project)? {
+        match target::Target::new(&root, &config) {
+            Ok(target) => targets.push(target),
+            Err(e) => log::warn!("Skipping target `{}`: {}", config.name, e),
+        }
+    }
+    Ok(targets)
+}
+
+/// Asks the user whether to continue.
+fn confirm() -> bool {
+    print!("Remove these packs? [y/N] ");
+    if io::stdout().flush().is_err() {
+        return false;
+    }
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+pub fn run(matches: &ArgMatches) -> ExitCode {
+    let project = match Project::current() {
+        Ok(project) => project,
+        Err(e) => {
+            e.log();
+            return ExitCode::FAILURE;
+        }
+    };
+    let targets = match targets(&project) {
+        Ok(targets) => targets,
+        Err(e) => {
+            e.log();
+            return ExitCode::FAILURE;
+        }
+    };
+    let ids: Vec<Uuid> = Pack::VALUES
+        .iter()
+        .filter_map(|pack| project.uuids.of(pack).header)
+        .collect();
+
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    let mut files: Vec<PathBuf> = Vec::new();
+    for target in &targets {
+        for dir in target.pack_dirs() {
+            match sync::installed(&dir, &project.id) {
+                Ok(installed) => dirs.extend(installed),
+                Err(e) => log::warn!("Failed to read {}: {}", dir.display(), e),
+            }
+        }
+        for world in target.worlds() {
+            match world::referencing(&world, &ids) {
+                Ok(referencing) => files.extend(referencing),
+                Err(e) => log::warn!("Failed to read packs of {}: {}", world.display(), e),
+            }
+        }
+    }
+    dirs.sort();
+    dirs.dedup();
+    files.sort();
+    files.dedup();
+
+    if dirs.is_empty() && files.is_empty() {
+        log::info!("No synced packs found");
+        return ExitCode::SUCCESS;
+    }
+    for dir in &dirs {
+        println!("Remove {}", dir.display());
+    }
+    for file in &files {
+        println!("Update {}", file.display());
+    }
+    if !matches.get_flag("yes") && !confirm() {
+        log::info!("Aborted");
+        return ExitCode::FAILURE;
+    }
+
+    let mut success = true;
+    for dir in &dirs {
+        match fs::remove_dir_all(dir) {
+            Ok(_) => log::info!("Removed {}", dir.display()),
+            Err(e) => {
+                log::error!("Failed to remove {}: {}", dir.display(), e);
+                success = false;
+            }
+        }
+    }
+    for file in &files {
+        match world::unregister(file, &ids) {
+            Ok(_) => log::info!("Removed packs from {}", file.display()),
+            Err(e) => {
+                log::error!("Failed to update {}: {}", file.display(), e);
+                success = false;
+            }
+        }
+    }
+
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -1,5 +1,6 @@
 use std::fs;
 
+use crate::manifest::ModuleType;
 use crate::paths;
 use crate::uuid::Uuids;
 use crate::Pack;
@@ -16,10 +17,19 @@ pub fn cmd() -> Command {
         )
         .arg(
             Arg::new("for")
-                .help("Whether the header or module UUID should be changed")
+                .help("Whether the header or module UUIDs should be changed")
                 .required(false)
                 .value_parser(["header", "module"])
-                .ignore_case(true),
+                .ignore_case(true)
+                .conflicts_with("module"),
+        )
+        .arg(
+            Arg::new("module")
+                .long("module")
+                .short('m')
+                .help("Only refresh the UUID of the module with the specified type (e.g. `script`)")
+                .value_name("TYPE")
+                .value_parser(ModuleType::VALUES.map(ModuleType::key)),
         )
         .arg(
             Arg::new("uuid")
@@ -34,21 +44,38 @@ pub fn run(matches: &ArgMatches) {
         .get_one("pack")
         .map(|p| vec![*p])
         .unwrap_or(Pack::VALUES.to_vec());
-    let kinds: Vec<String> = matches
-        .get_one("for")
-        .map(|k: &String| vec![k.clone()])
-        .unwrap_or_else(|| vec!["header".to_string(), "module".to_string()]);
+    let target = match (
+        matches.get_one::<String>("for").map(String::as_str),
+        matches.get_one::<String>("module"),
+    ) {
+        (_, Some(key)) => Target::Module(
+            ModuleType::from_key(key).expect("value parser only accepts module types"),
+        ),
+        (Some("header"), None) => Target::Header,
+        (Some(_), None) => Target::Modules,
+        (None, None) => Target::All,
+    };
     let uuid: Option<&libuuid::Uuid> = matches.get_one("uuid");
 
     let path = paths::root().join(paths::uuids());
     let data = fs::read_to_string(path).expect("cannot read UUIDs file");
     let mut uuids: Uuids = toml::from_str(&data).expect("invalid TOML");
 
-    update(&mut uuids, packs, kinds, uuid);
+    update(&mut uuids, packs, target, uuid);
     fs::write(paths::root().join(paths::uuids()), uuids.to_string()).expect("failed to save UUIDs");
 }
 
-fn update(uuids: &mut Uuids, packs: Vec<Pack>, kinds: Vec<String>, uuid: Option<&libuuid::Uuid>) {
+/// The UUIDs to refresh.
+#[derive(Clone, Copy)]
+enum Target {
+    All,
+    Header,
+    Modules,
+    Module(ModuleType),
+}
+
+fn update(uuids: &mut Uuids, packs: Vec<Pack>, target: Target, uuid: Option<&libuuid::Uuid>) {
+    let mut refreshed_module = false;
     for data in vec![
         (&mut uuids.bp, Pack::Behavior),
         (&mut uuids.rp, Pack::Resource),
@@ -58,13 +85,35 @@ fn update(uuids: &mut Uuids, packs: Vec<Pack>, kinds: Vec<String>, uuid: Option<
     .into_iter()
     .filter(|i| packs.contains(&i.1))
     {
-        if kinds.contains(&"header".to_string()) {
+        if matches!(target, Target::All | Target::Header) {
             data.0.update_header(uuid.copied());
             log::info!("Refreshed header UUID for {}", data.1);
         }
-        if kinds.contains(&"module".to_string()) {
+        if matches!(target, Target::All | Target::Modules) {
             data.0.update_module(uuid.copied());
             log::info!("Refreshed module UUID for {}", data.1);
+            let keys: Vec<String> = data.0.modules.keys().cloned().collect();
+            for key in keys {
+                data.0.modules.insert(
+                    key.clone(),
+                    uuid.copied().unwrap_or_else(libuuid::Uuid::new_v4),
+                );
+                log::info!("Refreshed {} module UUID for {}", key, data.1);
+            }
+        }
+        if let Target::Module(kind) = target {
+            // other modules are only refreshed if the pack has them
+            if kind == ModuleType::primary(data.1) || data.0.module_of(data.1, kind).is_some() {
+                data.0.update_module_of(data.1, kind, uuid.copied());
+                log::info!("Refreshed {} module UUID for {}", kind.key(), data.1);
+                refreshed_module = true;
+            }
         }
     }
+    if let (Target::Module(kind), false) = (target, refreshed_module) {
+        log::warn!(
+            "None of the selected packs has a `{}` module; no UUID was refreshed",
+            kind.key()
+        );
+    }
 }
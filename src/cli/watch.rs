@@ -1,5 +1,8 @@
+use super::build;
 use super::prelude::*;
-use crate::{paths, Project};
+use crate::artifacts;
+use crate::exclude::{self, Filter};
+use crate::{paths, Pack, Project};
 use clap::{ArgMatches, Command};
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
@@ -16,16 +19,26 @@ pub fn cmd() -> Command {
         .arg_build_opts()
 }
 
-pub fn run(_matches: &ArgMatches) -> ExitCode {
-    let project = Project::current().unwrap();
-    trigger_on_change(&project, |paths, root| {
+pub fn run(matches: &ArgMatches) -> ExitCode {
+    let project = match build::project(matches) {
+        Ok(project) => project,
+        Err(e) => {
+            e.log();
+            return ExitCode::FAILURE;
+        }
+    };
+    trigger_on_change(&project, |paths, _| {
         log::info!("Files changed: {:?}; Building project...", paths);
-        // TODO: debug/release mode
-        if let Err(e) = Project::from_root(&root.to_path_buf())
-            .expect("valid project root path")
-            .build()
-        {
-            log::error!("Unable to build project: {}", e);
+        match build::project(matches).and_then(|mut p| p.build().map(|report| (p, report))) {
+            Ok((project, report)) => artifacts::History::update(
+                &paths::root(),
+                report.addon.into_iter().collect(),
+                project.config.build.retain,
+            ),
+            Err(e) => {
+                log::error!("Unable to build project");
+                e.log();
+            }
         };
     });
     ExitCode::SUCCESS
@@ -72,6 +85,17 @@ where
         }
     }
 
+    let filters: Vec<Filter> = Pack::VALUES
+        .into_iter()
+        .map(|pack| match project.filter(&paths::root(), pack) {
+            Ok(filter) => filter,
+            Err(e) => {
+                e.log();
+                std::process::exit(1);
+            }
+        })
+        .collect();
+
     log::info!("Listening for changes ...");
 
     loop {
@@ -99,7 +123,7 @@ where
             .iter()
             .filter(|p| !p.starts_with(paths::root()))
             .cloned();
-        let mut paths = remove_ignored_files(&paths::root(), &paths[..]);
+        let mut paths = remove_ignored_files(&paths::root(), &filters, &paths[..]);
         paths.extend(any_external_paths);
 
         if !paths.is_empty() {
@@ -108,11 +132,22 @@ where
     }
 }
 
-fn remove_ignored_files(_root: &Path, paths: &[PathBuf]) -> Vec<PathBuf> {
-    // TODO: gitignore, see: https://github.com/rust-lang/mdBook/blob/master/src/cmd/watch.rs#L60
+/// Removes the paths within `root` which are ignored by the `.allayignore` file or excluded from a pack.
+fn remove_ignored_files(root: &Path, filters: &[Filter], paths: &[PathBuf]) -> Vec<PathBuf> {
     if paths.is_empty() {
-        Vec::new()
-    } else {
-        paths.iter().map(|path| path.to_path_buf()).collect()
+        return Vec::new();
     }
+    let ignore = exclude::allayignore(root);
+    paths
+        .iter()
+        .filter(|path| path.starts_with(root))
+        .filter(|path| {
+            let is_dir = path.is_dir();
+            !ignore.matched_path_or_any_parents(path, is_dir).is_ignore()
+                && !filters
+                    .iter()
+                    .any(|filter| filter.is_excluded(path, is_dir))
+        })
+        .cloned()
+        .collect()
 }
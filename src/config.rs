@@ -2,11 +2,18 @@
 
 // TODO: `serde(default)`s
 
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::NonZeroUsize,
+    path::PathBuf,
+};
 
 use crate::{
     localization::{Language, LanguageGroups, OptionallyLocalized},
-    manifest::{BaseGameVersion, Capabilities},
+    manifest::{BaseGameVersion, Capabilities, ModuleType},
+    paths,
+    skins::Skin,
+    Pack,
 };
 use serde::Deserialize;
 
@@ -43,6 +50,17 @@ pub struct Config {
     #[serde(default)]
     pub build: Build,
 
+    /// Named sets of overrides selected with the `--profile` option.
+    ///
+    /// The `dev` and `release` profiles are used for `--debug` and `--release` respectively and do not
+    /// need to be defined.
+    #[serde(default)]
+    pub profile: HashMap<String, Profile>,
+
+    /// Configure the `sync` command.
+    #[serde(default)]
+    pub sync: Sync,
+
     /// Plugins are executable which transform the packs as a process of the build.
     ///
     /// Plugins are executed in the order in which they are specified.
@@ -69,7 +87,8 @@ pub struct Config {
     #[serde(default)]
     pub wt: WT,
 
-    /// Section containing optional features that can be enabled in Minecraft.
+    /// Deprecated section of optional features that can be enabled in Minecraft. Use `[BP.capabilities]`
+    /// and `[RP.capabilities]` instead.
     #[serde(default)]
     pub capabilities: Option<Capabilities>,
 }
@@ -78,9 +97,51 @@ impl Config {
     pub fn from_str(s: &str) -> Result<Self, toml::de::Error> {
         toml::from_str(s)
     }
+
+    /// Returns the name and the description overrides of `pack`.
+    pub fn overrides(
+        &self,
+        pack: Pack,
+    ) -> (
+        Option<&OptionallyLocalized<String>>,
+        Option<&OptionallyLocalized<String>>,
+    ) {
+        match pack {
+            Pack::Behavior => (self.bp.name.as_ref(), self.bp.description.as_ref()),
+            Pack::Resource => (self.rp.name.as_ref(), self.rp.description.as_ref()),
+            Pack::Skin => (self.sp.name.as_ref(), self.sp.description.as_ref()),
+            Pack::WorldTemplate => (self.wt.name.as_ref(), self.wt.description.as_ref()),
+        }
+    }
+
+    /// Moves the capabilities of the deprecated top-level `[capabilities]` section into the sections of the
+    /// packs supporting them. Returns `true` if the section was used.
+    pub fn migrate_capabilities(&mut self) -> bool {
+        let Some(capabilities) = self.capabilities.take() else {
+            return false;
+        };
+        self.bp.capabilities.extend(&capabilities, Pack::Behavior);
+        self.rp.capabilities.extend(&capabilities, Pack::Resource);
+        true
+    }
+
+    /// Returns whether the generated manifests and `skins.json` are pretty-printed.
+    pub fn pretty(&self) -> bool {
+        self.build.pretty.unwrap_or(self.debug)
+    }
+
+    /// Returns the `include` and `exclude` patterns of the section of `pack`.
+    pub fn patterns(&self, pack: Pack) -> (&[String], &[String]) {
+        match pack {
+            Pack::Behavior => (&self.bp.include, &self.bp.exclude),
+            Pack::Resource => (&self.rp.include, &self.rp.exclude),
+            Pack::Skin => (&self.sp.include, &self.sp.exclude),
+            Pack::WorldTemplate => (&self.wt.include, &self.wt.exclude),
+        }
+    }
 }
 
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "kebab-case")]
 #[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
@@ -88,6 +149,213 @@ pub struct Build {
     /// Directories to watch besides from `src` when using the `watch` command.
     #[serde(default)]
     pub extra_watch_dirs: Vec<PathBuf>,
+
+    /// Whether to reuse the bundles of packs that did not change since the previous build.
+    #[serde(default = "enabled")]
+    pub incremental: bool,
+
+    /// Whether to reuse the bundles of packs when plugins are run.
+    ///
+    /// Only the scripts and the arguments of plugins referring to files are tracked. Every pack is rebuilt
+    /// when plugins are used unless this is enabled.
+    #[serde(default)]
+    pub cache_plugins: bool,
+
+    /// Whether to pretty-print the generated manifests and `skins.json`.
+    ///
+    /// Defaults to the `debug` field. These files are not minified when enabled.
+    #[serde(default)]
+    pub pretty: Option<bool>,
+
+    /// The directory the add-on and the bundles of the packs are written to relative to the project root.
+    #[serde(default = "default_output")]
+    pub output: PathBuf,
+
+    /// Template for the file name of the add-on without its extension.
+    ///
+    /// The placeholders `{name}`, `{version}` and `{profile}` are replaced with the name of the project,
+    /// its version and the build profile.
+    #[serde(default = "default_addon_name")]
+    pub addon_name: String,
+
+    /// Template for the file names of the bundles of the packs without their extension.
+    ///
+    /// Besides from the placeholders of `addon-name`, `{pack}` is replaced with the short name of the pack
+    /// (e.g. `BP`).
+    #[serde(default = "default_pack_name")]
+    pub pack_name: String,
+
+    /// The number of builds whose artifacts are kept in the output directory.
+    ///
+    /// Artifacts of older builds are removed. Every artifact is kept when unset.
+    #[serde(default)]
+    pub retain: Option<NonZeroUsize>,
+}
+
+impl Default for Build {
+    fn default() -> Self {
+        Self {
+            extra_watch_dirs: Vec::new(),
+            incremental: true,
+            cache_plugins: false,
+            pretty: None,
+            output: default_output(),
+            addon_name: default_addon_name(),
+            pack_name: default_pack_name(),
+            retain: None,
+        }
+    }
+}
+
+fn enabled() -> bool {
+    true
+}
+
+fn default_output() -> PathBuf {
+    PathBuf::from("dist")
+}
+
+fn default_addon_name() -> String {
+    String::from("{name}-{version}")
+}
+
+fn default_pack_name() -> String {
+    String::from("{name}-{version}-{pack}")
+}
+
+/// Name of the profile used for `--debug` builds.
+pub const DEV_PROFILE: &str = "dev";
+
+/// Name of the profile used for `--release` builds.
+pub const RELEASE_PROFILE: &str = "release";
+
+/// Overrides applied to the configuration when building with a profile.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+pub struct Profile {
+    /// Whether to build in debug mode.
+    ///
+    /// Defaults to `true` for the `dev` profile, to `false` for the `release` profile and to the top-level
+    /// `debug` field for every other profile.
+    #[serde(default)]
+    pub debug: Option<bool>,
+
+    /// Environment variables which are added to those defined in the `[env]` section or replace them.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+
+    /// The names of the plugins to run. Every plugin is run when unset.
+    #[serde(default)]
+    pub plugins: Option<Vec<String>>,
+
+    /// Overrides the `pretty` field of the `[build]` section.
+    #[serde(default)]
+    pub pretty: Option<bool>,
+
+    /// Overrides the `output` field of the `[build]` section.
+    #[serde(default)]
+    pub output: Option<PathBuf>,
+
+    /// Overrides the `addon-name` field of the `[build]` section.
+    #[serde(default)]
+    pub addon_name: Option<String>,
+
+    /// Overrides the `pack-name` field of the `[build]` section.
+    #[serde(default)]
+    pub pack_name: Option<String>,
+
+    /// Overrides the version of the project used for the packs.
+    #[serde(default)]
+    pub version: Option<Version>,
+}
+
+/// Locations the packs can be installed to with the `sync` command.
+///
+/// The same format is used for the user-level `sync.toml` file in Allay's configuration directory which
+/// keeps machine-specific paths out of the project.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+pub struct Sync {
+    /// The named targets selected with `allay sync <TARGET>`.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [[sync.target]]
+    /// name = "server"
+    /// type = "bds"
+    /// path = "../server"
+    /// packs = ["BP", "RP"]
+    /// ```
+    #[serde(default)]
+    #[serde(rename = "target")]
+    pub targets: Vec<SyncTarget>,
+}
+
+impl Sync {
+    /// Loads the user-level targets from `s`.
+    pub fn from_str(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+}
+
+/// A location the packs can be installed to with the `sync` command.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+pub struct SyncTarget {
+    /// The name used to select the target.
+    pub name: String,
+
+    /// The kind of the target.
+    #[serde(rename = "type")]
+    #[serde(default)]
+    pub kind: SyncTargetKind,
+
+    /// The path of the target.
+    ///
+    /// Relative paths are resolved against the project root for targets of the configuration and against
+    /// the directory containing `sync.toml` for user-level targets.
+    pub path: PathBuf,
+
+    /// The packs installed to the target. Every pack supported by the target is installed by default.
+    #[serde(default)]
+    pub packs: Option<Vec<Pack>>,
+
+    /// The name or folder of a world within `minecraftWorlds` to install the packs into and apply them to.
+    ///
+    /// Only used for targets of type `com.mojang`.
+    #[serde(default)]
+    pub world: Option<String>,
+
+    /// Experiments to enable or disable in the world of the server (e.g. `{ gametest = true }`).
+    ///
+    /// Only used for targets of type `bds`.
+    #[serde(default)]
+    pub experiments: BTreeMap<String, bool>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+pub enum SyncTargetKind {
+    /// A `com.mojang` directory of a Minecraft installation which contains the `development_*_packs`
+    /// directories.
+    #[default]
+    #[serde(rename = "com.mojang")]
+    ComMojang,
+
+    /// The root directory of a Bedrock Dedicated Server.
+    #[serde(rename = "bds")]
+    Bds,
+
+    /// A directory the packs are extracted into.
+    #[serde(rename = "directory")]
+    Directory,
 }
 
 /// Metadata of the Allay project.
@@ -308,6 +576,155 @@ pub struct BP {
     /// other by default.
     #[serde(default)]
     pub dependencies: Vec<Dependency>,
+
+    /// Settings for behavior packs of type `script`. The default settings are used if the section is
+    /// omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [BP]
+    /// type = "script"
+    ///
+    /// [BP.script]
+    /// entry = "scripts/main.js"
+    /// ```
+    #[serde(default)]
+    pub script: Option<Script>,
+
+    /// The types of the modules of the pack.
+    ///
+    /// Defaults to `["data"]` and for behavior packs of type `script` to `["data", "script"]` or to
+    /// `["script"]` if `data-module` is disabled.
+    #[serde(default)]
+    pub modules: Option<Vec<ModuleType>>,
+
+    /// Optional features that can be enabled in Minecraft.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [BP.capabilities]
+    /// chemistry = true
+    /// ```
+    #[serde(default)]
+    pub capabilities: Capabilities,
+
+    /// Glob patterns of the files which are included in the pack (e.g. `["**/*.json", "textures/**"]`).
+    ///
+    /// The patterns are matched against the paths of the files relative to the pack directory (e.g.
+    /// `src/BP`). Every file is included when no patterns are given. Files matching an `exclude` pattern or
+    /// the `.allayignore` file in the project root are never included. The same applies to the `include`
+    /// and `exclude` fields of the other packs.
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// Glob patterns of the files which are not included in the pack (e.g. `["**/*.psd", "**/.DS_Store"]`).
+    ///
+    /// A pattern matching a directory excludes every file within it. See [`BP::include`] for how the
+    /// patterns are matched.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl BP {
+    /// Returns the script settings if the behavior pack is of type `script`.
+    pub fn script_settings(&self) -> Option<Script> {
+        match self.kind {
+            BehaviorPackType::Script => Some(self.script.clone().unwrap_or_default()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+pub struct Script {
+    /// The path of the script that is run when the pack is loaded relative to the behavior pack (e.g.
+    /// `scripts/main.js`).
+    ///
+    /// TypeScript files (e.g. `scripts/main.ts`) are compiled and the compiled JavaScript file is used as the
+    /// entry. Defaults to `scripts/main.ts` if that file exists and to `scripts/main.js` otherwise.
+    #[serde(default)]
+    pub entry: Option<String>,
+
+    /// Whether to add a `data` module next to the `script` module to the manifest.
+    #[serde(default = "enabled")]
+    pub data_module: bool,
+
+    /// The compiler used when the behavior pack contains TypeScript files.
+    #[serde(default)]
+    pub compiler: Compiler,
+
+    /// The path of the compiler's executable relative to the project root.
+    ///
+    /// Defaults to the executable in `node_modules/.bin` if present and to the one found in the `PATH`
+    /// otherwise.
+    #[serde(default)]
+    pub compiler_path: Option<PathBuf>,
+}
+
+impl Default for Script {
+    fn default() -> Self {
+        Self {
+            entry: None,
+            data_module: true,
+            compiler: Compiler::default(),
+            compiler_path: None,
+        }
+    }
+}
+
+impl Script {
+    /// Returns the configured or the default entry which may be a TypeScript file.
+    pub fn source_entry(&self) -> String {
+        match &self.entry {
+            Some(entry) => entry.clone(),
+            None if paths::root()
+                .join(paths::src_bp())
+                .join("scripts/main.ts")
+                .is_file() =>
+            {
+                String::from("scripts/main.ts")
+            }
+            None => String::from("scripts/main.js"),
+        }
+    }
+
+    /// Returns the path of the JavaScript file that is run when the pack is loaded relative to the behavior
+    /// pack.
+    pub fn entry(&self) -> String {
+        let entry = self.source_entry();
+        match entry.strip_suffix(".ts") {
+            Some(stem) => format!("{}.js", stem),
+            None => entry,
+        }
+    }
+}
+
+/// A compiler for TypeScript files.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+pub enum Compiler {
+    /// Bundles the entry and the modules it imports into a single file using [esbuild](https://esbuild.github.io/).
+    #[default]
+    Esbuild,
+
+    /// Compiles every TypeScript file using the [TypeScript compiler](https://www.typescriptlang.org/).
+    Tsc,
+}
+
+impl Compiler {
+    /// Returns the name of the compiler's executable.
+    pub fn executable(&self) -> &'static str {
+        match self {
+            Self::Esbuild => "esbuild",
+            Self::Tsc => "tsc",
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -323,11 +740,11 @@ pub struct RP {
     #[serde(default)]
     pub custom_pack_icon: bool,
 
-    /// Override name for behavior pack.
+    /// Override name for resource pack.
     #[serde(default)]
     pub name: Option<OptionallyLocalized<String>>,
 
-    /// Override description for behavior pack.
+    /// Override description for resource pack.
     #[serde(default)]
     pub description: Option<OptionallyLocalized<String>>,
 
@@ -337,6 +754,31 @@ pub struct RP {
     /// other by default.
     #[serde(default)]
     pub dependencies: Vec<Dependency>,
+
+    /// The types of the modules of the pack.
+    ///
+    /// Defaults to `["resources"]`.
+    #[serde(default)]
+    pub modules: Option<Vec<ModuleType>>,
+
+    /// Optional features that can be enabled in Minecraft.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [RP.capabilities]
+    /// raytraced = true
+    /// ```
+    #[serde(default)]
+    pub capabilities: Capabilities,
+
+    /// Glob patterns of the files which are included in the pack. See [`BP::include`].
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// Glob patterns of the files which are not included in the pack. See [`BP::exclude`].
+    #[serde(default)]
+    pub exclude: Vec<String>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -352,20 +794,42 @@ pub struct SP {
     #[serde(default)]
     pub custom_pack_icon: bool,
 
-    /// Override name for behavior pack.
+    /// Override name for skin pack.
     #[serde(default)]
     pub name: Option<OptionallyLocalized<String>>,
 
-    /// Override description for behavior pack.
+    /// Override description for skin pack.
     #[serde(default)]
     pub description: Option<OptionallyLocalized<String>>,
 
-    /// Define extra dependencies.
-    ///
-    /// Note that the behavior pack and the resource pack definined in the same project will depend on each
-    /// other by default.
+    /// Define dependencies on other packs.
     #[serde(default)]
     pub dependencies: Vec<Dependency>,
+
+    /// The types of the modules of the pack.
+    ///
+    /// Skin packs only support the default `["skin_pack"]`.
+    #[serde(default)]
+    pub modules: Option<Vec<ModuleType>>,
+
+    /// The name identifying the skin pack in `skins.json` and the language keys of the skins.
+    ///
+    /// Defaults to the ASCII letters and digits of the project name. A `serialize-name` in the
+    /// `skins.toml` file takes precedence.
+    #[serde(default)]
+    pub serialize_name: Option<String>,
+
+    /// The skins of the skin pack which are combined with those defined in `src/SP/skins.toml`.
+    #[serde(default)]
+    pub skins: Vec<Skin>,
+
+    /// Glob patterns of the files which are included in the pack. See [`BP::include`].
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// Glob patterns of the files which are not included in the pack. See [`BP::exclude`].
+    #[serde(default)]
+    pub exclude: Vec<String>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -385,17 +849,41 @@ pub struct WT {
     #[serde(default)]
     pub exclude_bp: bool,
 
+    /// Whether to generate a random seed every time the template is loaded and allow the player to change
+    /// the seed before creating a new world.
     #[serde(default)]
     pub allow_random_seed: bool,
 
+    /// The version of the base game the world template was created for (e.g. `[1, 20, 50]`) or `"*"` if
+    /// the template is version agnostic.
+    ///
+    /// Must not be lower than `project.min-engine-version`.
     #[serde(default)]
     pub base_game_version: BaseGameVersion,
 
-    /// Override name for behavior pack.
+    /// Whether to prevent players from modifying the options of worlds created from the template.
+    #[serde(default)]
+    pub lock_template_options: bool,
+
+    /// Override name for world template.
     #[serde(default)]
     pub name: Option<OptionallyLocalized<String>>,
 
-    /// Override description for behavior pack.
+    /// Override description for world template.
     #[serde(default)]
     pub description: Option<OptionallyLocalized<String>>,
+
+    /// The types of the modules of the pack.
+    ///
+    /// World templates only support the default `["world_template"]`.
+    #[serde(default)]
+    pub modules: Option<Vec<ModuleType>>,
+
+    /// Glob patterns of the files which are included in the pack. See [`BP::include`].
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// Glob patterns of the files which are not included in the pack. See [`BP::exclude`].
+    #[serde(default)]
+    pub exclude: Vec<String>,
 }
@@ -28,6 +28,22 @@ pub enum Notification {
     ComMojangNotFoundAndroid,
     ComMojangNotFoundWindows,
     ComMojangNotFoundLinux,
+    NotInAProject,
+    InvalidProjectSetup,
+    InvalidConfig,
+    InvalidVersion,
+    MissingUuid,
+    InvalidDependency,
+    PluginFailed,
+    InvalidFilter,
+    IoFailure,
+    ZipFailure,
+    MissingScriptEntry,
+    InvalidCapability,
+    IneffectiveOverride,
+    MissingSkinTexture,
+    InvalidJson,
+    CompilerFailed,
 }
 
 impl Diagnostic for Notification {
@@ -43,6 +59,24 @@ impl Diagnostic for Notification {
             Self::ComMojangNotFoundAndroid
             | Self::ComMojangNotFoundWindows
             | Self::ComMojangNotFoundLinux => "The `com.mojang` folder cannot be found",
+            Self::NotInAProject => {
+                "Cannot find an `allay.toml` file in this or any parent directory"
+            }
+            Self::InvalidProjectSetup => "Files in the `.allay` directory are missing or corrupted",
+            Self::InvalidConfig => "A configuration file is invalid",
+            Self::InvalidVersion => "A version is invalid",
+            Self::MissingUuid => "A UUID required for the manifest is missing",
+            Self::InvalidDependency => "A dependency is invalid",
+            Self::PluginFailed => "A plugin failed",
+            Self::InvalidFilter => "The filter of a plugin cannot be evaluated",
+            Self::IoFailure => "Cannot read or write a file",
+            Self::ZipFailure => "Cannot zip a pack",
+            Self::MissingScriptEntry => "The entry of the behavior pack's scripts does not exist",
+            Self::InvalidCapability => "A capability is not supported by the pack and is ignored",
+            Self::IneffectiveOverride => "The name or description override of a pack has no effect",
+            Self::MissingSkinTexture => "The texture of a skin does not exist",
+            Self::InvalidJson => "A JSON file of a pack or a file converted to JSON is invalid",
+            Self::CompilerFailed => "The TypeScript files of the behavior pack cannot be compiled",
         }
     }
 
@@ -54,6 +88,22 @@ impl Diagnostic for Notification {
             Self::ComMojangNotFoundAndroid => Some(include_str!("com_mojang_not_found_android.md")),
             Self::ComMojangNotFoundWindows => None,
             Self::ComMojangNotFoundLinux => None,
+            Self::NotInAProject => Some(include_str!("not_in_a_project.md")),
+            Self::InvalidProjectSetup => Some(include_str!("invalid_project_setup.md")),
+            Self::InvalidConfig => Some(include_str!("invalid_config.md")),
+            Self::InvalidVersion => Some(include_str!("invalid_version.md")),
+            Self::MissingUuid => Some(include_str!("missing_uuid.md")),
+            Self::InvalidDependency => Some(include_str!("invalid_dependency.md")),
+            Self::PluginFailed => Some(include_str!("plugin_failed.md")),
+            Self::InvalidFilter => Some(include_str!("invalid_filter.md")),
+            Self::IoFailure => Some(include_str!("io_failure.md")),
+            Self::ZipFailure => Some(include_str!("zip_failure.md")),
+            Self::MissingScriptEntry => Some(include_str!("missing_script_entry.md")),
+            Self::InvalidCapability => Some(include_str!("invalid_capability.md")),
+            Self::IneffectiveOverride => Some(include_str!("ineffective_override.md")),
+            Self::MissingSkinTexture => Some(include_str!("missing_skin_texture.md")),
+            Self::InvalidJson => Some(include_str!("invalid_json.md")),
+            Self::CompilerFailed => Some(include_str!("compiler_failed.md")),
         }
     }
 
@@ -65,6 +115,22 @@ impl Diagnostic for Notification {
             Self::ComMojangNotFoundAndroid => 4,
             Self::ComMojangNotFoundWindows => 5,
             Self::ComMojangNotFoundLinux => 6,
+            Self::NotInAProject => 7,
+            Self::InvalidProjectSetup => 8,
+            Self::InvalidConfig => 9,
+            Self::InvalidVersion => 10,
+            Self::MissingUuid => 11,
+            Self::InvalidDependency => 12,
+            Self::PluginFailed => 13,
+            Self::InvalidFilter => 14,
+            Self::IoFailure => 15,
+            Self::ZipFailure => 16,
+            Self::MissingScriptEntry => 17,
+            Self::InvalidCapability => 18,
+            Self::IneffectiveOverride => 19,
+            Self::MissingSkinTexture => 20,
+            Self::InvalidJson => 21,
+            Self::CompilerFailed => 22,
         }
     }
 
@@ -87,6 +153,22 @@ impl Diagnostic for Notification {
             4 => Some(Self::ComMojangNotFoundAndroid),
             5 => Some(Self::ComMojangNotFoundWindows),
             6 => Some(Self::ComMojangNotFoundLinux),
+            7 => Some(Self::NotInAProject),
+            8 => Some(Self::InvalidProjectSetup),
+            9 => Some(Self::InvalidConfig),
+            10 => Some(Self::InvalidVersion),
+            11 => Some(Self::MissingUuid),
+            12 => Some(Self::InvalidDependency),
+            13 => Some(Self::PluginFailed),
+            14 => Some(Self::InvalidFilter),
+            15 => Some(Self::IoFailure),
+            16 => Some(Self::ZipFailure),
+            17 => Some(Self::MissingScriptEntry),
+            18 => Some(Self::InvalidCapability),
+            19 => Some(Self::IneffectiveOverride),
+            20 => Some(Self::MissingSkinTexture),
+            21 => Some(Self::InvalidJson),
+            22 => Some(Self::CompilerFailed),
             _ => None,
         }
     }
@@ -99,6 +181,22 @@ impl Diagnostic for Notification {
             Self::ComMojangNotFoundAndroid => Kind::Error,
             Self::ComMojangNotFoundWindows => Kind::Error,
             Self::ComMojangNotFoundLinux => Kind::Error,
+            Self::NotInAProject => Kind::Error,
+            Self::InvalidProjectSetup => Kind::Error,
+            Self::InvalidConfig => Kind::Error,
+            Self::InvalidVersion => Kind::Error,
+            Self::MissingUuid => Kind::Error,
+            Self::InvalidDependency => Kind::Error,
+            Self::PluginFailed => Kind::Error,
+            Self::InvalidFilter => Kind::Error,
+            Self::IoFailure => Kind::Error,
+            Self::ZipFailure => Kind::Error,
+            Self::MissingScriptEntry => Kind::Error,
+            Self::InvalidCapability => Kind::Warning,
+            Self::IneffectiveOverride => Kind::Warning,
+            Self::MissingSkinTexture => Kind::Error,
+            Self::InvalidJson => Kind::Error,
+            Self::CompilerFailed => Kind::Error,
         }
     }
 }
@@ -112,7 +210,7 @@ impl Display for Notification {
             brief_description = self.brief_description(),
             extra = match self.extensive_description() {
                 Some(_) => format!(
-                    ". Use `allay explain W{}` to gather further information",
+                    ". Use `allay explain {}` to gather further information",
                     self.id()
                 ),
                 None => String::new(),
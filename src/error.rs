@@ -1,13 +1,209 @@
+use crate::diagnostic::{Diagnostic, Notification};
+use crate::paths;
 use crate::Pack;
+use std::io;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
+/// Errors that can occur while loading or building a project.
+///
+/// Each error corresponds to a [`Notification`] whose extensive description can be shown with
+/// `allay explain`.
 #[derive(thiserror::Error, Debug)]
-pub enum Error {
+pub enum BuildError {
     #[error("Cannot find project")]
     NotInAProject,
 
+    #[error("Invalid project setup")]
+    InvalidProjectSetup,
+
+    #[error("{}: {message}", location(.path, *.line, *.column))]
+    InvalidConfig {
+        path: PathBuf,
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+
+    #[error("Invalid version `{version}`: {reason}")]
+    InvalidVersion { version: String, reason: String },
+
     #[error("Missing UUID for {0}")]
     MissingUuid(Pack),
 
-    #[error("Invalid project setup")]
-    InvalidProjectSetup,
+    #[error("Invalid dependency `{dependency}`: {reason}")]
+    InvalidDependency { dependency: String, reason: String },
+
+    #[error("Script entry `{0}` does not exist")]
+    MissingScriptEntry(String),
+
+    #[error("Texture `{texture}` of skin `{skin}` does not exist")]
+    MissingSkinTexture { skin: String, texture: String },
+
+    #[error("{}: {message}", location(.path, *.line, *.column))]
+    InvalidJson {
+        path: PathBuf,
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+
+    #[error("Compiler {compiler} failed: {reason}")]
+    CompilerFailed { compiler: String, reason: String },
+
+    #[error("Plugin {name} failed: {reason}")]
+    PluginFailed { name: String, reason: String },
+
+    #[error("Invalid filter `{filter}`: {reason}")]
+    InvalidFilter { filter: String, reason: String },
+
+    #[error("{}: {source}", .path.display())]
+    Io { path: PathBuf, source: io::Error },
+
+    #[error("Failed to zip {}: {source}", .path.display())]
+    Zip {
+        path: PathBuf,
+        source: zip::result::ZipError,
+    },
+
+    #[error("Failed to build {pack}: {source}")]
+    Pack { pack: Pack, source: Box<BuildError> },
+}
+
+impl BuildError {
+    /// Returns a function which turns an IO error into a [`BuildError::Io`] for `path`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// fs::read_to_string(&path).map_err(BuildError::io(&path))?;
+    /// ```
+    pub fn io(path: impl AsRef<Path>) -> impl FnOnce(io::Error) -> Self {
+        let path = path.as_ref().to_path_buf();
+        move |source| Self::Io { path, source }
+    }
+
+    /// Returns a function which turns a zip error into a [`BuildError::Zip`] for `path`.
+    pub fn zip(path: impl AsRef<Path>) -> impl FnOnce(zip::result::ZipError) -> Self {
+        let path = path.as_ref().to_path_buf();
+        move |source| Self::Zip { path, source }
+    }
+
+    /// Returns a function which turns an error while walking `dir` into a [`BuildError::Io`] for the entry
+    /// that could not be read.
+    pub fn walk(dir: impl AsRef<Path>) -> impl FnOnce(walkdir::Error) -> Self {
+        let dir = dir.as_ref().to_path_buf();
+        move |e| Self::Io {
+            path: e.path().map(Path::to_path_buf).unwrap_or(dir),
+            source: e.into(),
+        }
+    }
+
+    /// Creates a [`BuildError::InvalidConfig`] for the file at `path` with the content `content`.
+    ///
+    /// `span` is the byte range within `content` the error refers to.
+    pub fn config(
+        path: impl AsRef<Path>,
+        content: &str,
+        span: Option<Range<usize>>,
+        message: impl ToString,
+    ) -> Self {
+        let (line, column) = position(content, span);
+        Self::InvalidConfig {
+            path: path.as_ref().to_path_buf(),
+            line,
+            column,
+            message: message.to_string().trim_end().to_string(),
+        }
+    }
+
+    /// Creates a [`BuildError::InvalidConfig`] for the project's `allay.toml` without a location.
+    pub fn invalid_config(message: impl ToString) -> Self {
+        Self::InvalidConfig {
+            path: paths::root().join(paths::config()),
+            line: None,
+            column: None,
+            message: message.to_string(),
+        }
+    }
+
+    /// Creates a [`BuildError::InvalidJson`] for the pack file at `path` with the content `content`.
+    ///
+    /// `span` is the byte range within `content` the error refers to.
+    pub fn json(
+        path: impl AsRef<Path>,
+        content: &str,
+        span: Option<Range<usize>>,
+        message: impl ToString,
+    ) -> Self {
+        let (line, column) = position(content, span);
+        Self::InvalidJson {
+            path: path.as_ref().to_path_buf(),
+            line,
+            column,
+            message: message.to_string().trim_end().to_string(),
+        }
+    }
+
+    /// Returns the notification which explains the error.
+    pub fn notification(&self) -> Notification {
+        match self {
+            Self::NotInAProject => Notification::NotInAProject,
+            Self::InvalidProjectSetup => Notification::InvalidProjectSetup,
+            Self::InvalidConfig { .. } => Notification::InvalidConfig,
+            Self::InvalidVersion { .. } => Notification::InvalidVersion,
+            Self::MissingUuid(_) => Notification::MissingUuid,
+            Self::InvalidDependency { .. } => Notification::InvalidDependency,
+            Self::PluginFailed { .. } => Notification::PluginFailed,
+            Self::MissingScriptEntry(_) => Notification::MissingScriptEntry,
+            Self::MissingSkinTexture { .. } => Notification::MissingSkinTexture,
+            Self::InvalidJson { .. } => Notification::InvalidJson,
+            Self::CompilerFailed { .. } => Notification::CompilerFailed,
+            Self::InvalidFilter { .. } => Notification::InvalidFilter,
+            Self::Io { .. } => Notification::IoFailure,
+            Self::Zip { .. } => Notification::ZipFailure,
+            Self::Pack { source, .. } => source.notification(),
+        }
+    }
+
+    /// Logs the error along with a hint on how to gather further information about it.
+    pub fn log(&self) {
+        log::error!("{}", self);
+        log::info!(
+            "Use `allay explain {}` to gather further information",
+            self.notification().id()
+        );
+    }
+
+    /// Wraps the error to state that it occurred while building `pack`.
+    pub fn in_pack(self, pack: Pack) -> Self {
+        match self {
+            Self::Pack { .. } => self,
+            _ => Self::Pack {
+                pack,
+                source: Box::new(self),
+            },
+        }
+    }
+}
+
+/// Returns the line and the column of the start of `span` within `content`.
+fn position(content: &str, span: Option<Range<usize>>) -> (Option<usize>, Option<usize>) {
+    match span {
+        Some(span) => {
+            let before = &content[..span.start.min(content.len())];
+            let line = before.matches('\n').count() + 1;
+            let column = before.len() - before.rfind('\n').map(|i| i + 1).unwrap_or(0) + 1;
+            (Some(line), Some(column))
+        }
+        None => (None, None),
+    }
+}
+
+fn location(path: &Path, line: Option<usize>, column: Option<usize>) -> String {
+    match (line, column) {
+        (Some(line), Some(column)) => format!("{}:{}:{}", path.display(), line, column),
+        (Some(line), None) => format!("{}:{}", path.display(), line),
+        _ => path.display().to_string(),
+    }
 }
@@ -0,0 +1,161 @@
+//! Filtering of the files that are included in packs.
+//!
+//! Each pack section accepts `include` and `exclude` lists of glob patterns matched against the path of a
+//! file relative to the pack directory (e.g. `textures/logo.psd`). A file is part of the pack if it matches
+//! any `include` pattern (or no patterns are given) and does not match any `exclude` pattern.
+//!
+//! Additionally, files matching the patterns of the `.allayignore` file in the project root are never
+//! included. The file uses the same syntax as `.gitignore` files and patterns are relative to the project
+//! root.
+
+use crate::paths;
+use crate::{BuildError, Pack};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The files included in a pack.
+#[derive(Clone, Debug)]
+pub struct Filter {
+    /// The directory of the pack the patterns are relative to.
+    dir: PathBuf,
+
+    /// The patterns of the `include` list or [`None`] if every file is included.
+    include: Option<GlobSet>,
+
+    /// The patterns of the `exclude` list.
+    exclude: GlobSet,
+
+    /// The patterns of the `.allayignore` file.
+    ignore: Gitignore,
+}
+
+impl Filter {
+    /// Creates the filter of `pack` for the project located at `root` using the `include` and `exclude`
+    /// patterns of its section in the configuration file.
+    pub fn new(
+        root: &Path,
+        pack: Pack,
+        include: &[String],
+        exclude: &[String],
+    ) -> Result<Self, BuildError> {
+        let section = format!("[{}]", pack.short_name());
+        Ok(Self {
+            dir: root.join(paths::src()).join(pack.short_name()),
+            include: if include.is_empty() {
+                None
+            } else {
+                Some(glob_set(&section, "include", include)?)
+            },
+            exclude: glob_set(&section, "exclude", exclude)?,
+            ignore: allayignore(root),
+        })
+    }
+
+    /// Returns `true` if the file or directory located at `path` or one of its parent directories is not
+    /// part of the pack.
+    ///
+    /// Directories are only excluded by `exclude` patterns and the `.allayignore` file as files within them
+    /// may still match an `include` pattern. Paths outside of the pack directory are never excluded.
+    pub fn is_excluded(&self, path: &Path, is_dir: bool) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.dir) else {
+            return false;
+        };
+        if relative.as_os_str().is_empty() {
+            return false;
+        }
+        let excluded = self
+            .ignore
+            .matched_path_or_any_parents(path, is_dir)
+            .is_ignore()
+            || relative
+                .ancestors()
+                .filter(|ancestor| !ancestor.as_os_str().is_empty())
+                .any(|ancestor| self.exclude.is_match(ancestor));
+        if excluded {
+            return true;
+        }
+        !is_dir
+            && self
+                .include
+                .as_ref()
+                .is_some_and(|include| !include.is_match(relative))
+    }
+
+    /// Returns the files within the pack directory that are part of the pack.
+    pub fn files(&self) -> io::Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        let walker = walkdir::WalkDir::new(&self.dir)
+            .into_iter()
+            .filter_entry(|entry| !self.is_excluded(entry.path(), entry.file_type().is_dir()));
+        for entry in walker {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                files.push(entry.into_path());
+            }
+        }
+        Ok(files)
+    }
+
+    /// Copies the files that are part of the pack into `dest`.
+    ///
+    /// Returns the number of files that were left out.
+    pub fn copy(&self, dest: &Path) -> Result<usize, BuildError> {
+        let files = self.files().map_err(BuildError::io(&self.dir))?;
+        fs::create_dir_all(dest).map_err(BuildError::io(dest))?;
+        for file in &files {
+            let target = dest.join(
+                file.strip_prefix(&self.dir)
+                    .expect("walked path is within the walked directory"),
+            );
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(BuildError::io(parent))?;
+            }
+            fs::copy(file, &target).map_err(BuildError::io(file))?;
+        }
+        let total = walkdir::WalkDir::new(&self.dir)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .count();
+        Ok(total.saturating_sub(files.len()))
+    }
+}
+
+/// Returns the patterns of the `.allayignore` file of the project located at `root`.
+///
+/// Invalid patterns are skipped with a warning.
+pub fn allayignore(root: &Path) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(root);
+    let path = root.join(paths::allayignore());
+    if path.is_file() {
+        if let Some(e) = builder.add(&path) {
+            log::warn!("Ignoring invalid patterns in {}: {}", path.display(), e);
+        }
+    }
+    builder.build().unwrap_or_else(|e| {
+        log::warn!("Ignoring {}: {}", path.display(), e);
+        Gitignore::empty()
+    })
+}
+
+fn glob_set(section: &str, field: &str, patterns: &[String]) -> Result<GlobSet, BuildError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| {
+            BuildError::invalid_config(format!(
+                "invalid pattern in `{}` of `{}`: {}",
+                field, section, e
+            ))
+        })?;
+        builder.add(glob);
+    }
+    builder.build().map_err(|e| {
+        BuildError::invalid_config(format!(
+            "invalid patterns in `{}` of `{}`: {}",
+            field, section, e
+        ))
+    })
+}
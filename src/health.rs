@@ -1,7 +1,10 @@
 //! Utilities for validating and fixing the project structure and data.
 
+use crate::diagnostic::Notification;
+use crate::localization::collect_user_translations;
 use crate::uuid::Uuids;
-use crate::{paths, uuid};
+use crate::{paths, uuid, Config, Pack};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -19,6 +22,7 @@ impl Health {
             self.check_internal(),
             self.check_uuids(),
             self.check_uuids_presence(),
+            self.check_overrides(),
         ] {
             if !success {
                 return false;
@@ -202,6 +206,63 @@ impl Health {
             }
         }
     }
+
+    /// Warns about name and description overrides of packs which have no effect.
+    ///
+    /// Overrides are ineffective if the pack has no content or if the pack defines the translation itself
+    /// in its `texts` directory.
+    pub fn check_overrides(&self) -> bool {
+        let path = self.root.join(paths::config());
+        let config = match fs::read_to_string(&path).map(|content| Config::from_str(&content)) {
+            Ok(Ok(config)) => config,
+            Ok(Err(e)) => {
+                log::error!("Invalid config file {}: {}", path.display(), e);
+                return false;
+            }
+            Err(e) => {
+                log::error!("Failed to read config file {}: {}", path.display(), e);
+                return false;
+            }
+        };
+
+        for pack in Pack::VALUES {
+            let (name, description) = config.overrides(pack);
+            let src = self.root.join(paths::src()).join(pack.short_name());
+            let texts = src.join("texts");
+            let user_translations = if texts.is_dir() {
+                collect_user_translations(&texts).unwrap_or_default()
+            } else {
+                HashMap::new()
+            };
+            for (field, key) in [
+                (name.map(|_| "name"), "pack.name"),
+                (description.map(|_| "description"), "pack.description"),
+            ] {
+                let Some(field) = field else {
+                    continue;
+                };
+                if !has_content(&src) {
+                    log::warn!(
+                        "`{}.{}` is set but the pack has no content",
+                        pack.short_name(),
+                        field
+                    );
+                } else if user_translations.contains_key(key) {
+                    log::warn!(
+                        "`{}.{}` is set but `{}` is defined in {}",
+                        pack.short_name(),
+                        field,
+                        key,
+                        texts.display()
+                    );
+                } else {
+                    continue;
+                }
+                log::warn!("{}", Notification::IneffectiveOverride);
+            }
+        }
+        true
+    }
 }
 
 /// Returns `true` when `dir` contains files or directories.
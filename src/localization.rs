@@ -29,6 +29,20 @@ pub enum OptionallyLocalized<T> {
     Unlocalized(T),
 }
 
+impl<T: Clone> OptionallyLocalized<T> {
+    /// Returns the localized values or maps the unlocalized value to `language`.
+    pub fn to_localized(&self, language: &Language) -> Localized<T> {
+        match self {
+            Self::Localized(m) => m.clone(),
+            Self::Unlocalized(value) => {
+                let mut map = HashMap::new();
+                map.insert(language.clone(), value.clone());
+                map
+            }
+        }
+    }
+}
+
 /// A group of languages used for fallbacks.
 pub type LanguageGroup = Vec<Language>;
 
@@ -190,7 +204,7 @@ impl LanguageGroups {
 
 /// Generates a `language.json` file which is just an array of all language IDs.
 pub fn generate_language_json(dir: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
-    let langs: Vec<String> = dir
+    let mut langs: Vec<String> = dir
         .read_dir()?
         .filter_map(Result::ok)
         .filter(|d| {
@@ -221,6 +235,7 @@ pub fn generate_language_json(dir: &PathBuf) -> Result<(), Box<dyn std::error::E
             }
         })
         .collect();
+    langs.sort();
     let json: serde_json::Value = serde_json::from_value(langs.into())?;
     let path = dir.join("languages.json");
     fs::write(path, json.to_string())?;
@@ -242,9 +257,16 @@ pub fn update_language_files(
     data: HashMap<String, Localized<String>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     log::debug!("translations: {:#?}", data);
-    for (key, target) in &data {
+    // sorted to keep the generated files identical across builds
+    let mut keys: Vec<&String> = data.keys().collect();
+    keys.sort();
+    for key in keys {
+        let target = &data[key];
+        let mut languages: Vec<&Language> = target.keys().collect();
+        languages.sort_by_key(|lang| lang.file_id());
         let mut covered: Vec<&Language> = Vec::new();
-        for (lang, translation) in target {
+        for lang in languages {
+            let translation = &target[lang];
             append_language_file(dir, lang, key, translation)?;
             covered.push(lang);
         }
@@ -43,25 +43,34 @@
 //                                              ==                                *****
 //                                                                                *****
 
+mod artifacts;
+mod cache;
 mod cli;
 mod config;
 mod diagnostic;
 mod error;
+mod exclude;
 mod filter;
 mod health;
 mod init;
 mod localization;
 mod manifest;
+mod minify;
+mod nbt;
 mod pack;
 mod paths;
 mod plugin;
 mod project;
+mod report;
 mod scaffolding;
+mod skins;
+mod transpile;
+mod typescript;
 mod uuid;
 
 use crate::init::init;
 use config::Config;
-use error::Error;
+use error::BuildError;
 use health::Health;
 use manifest::Manifest;
 use pack::Pack;
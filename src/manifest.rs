@@ -6,25 +6,54 @@
 
 use crate::config;
 use crate::config::BehaviorPackType;
-use crate::Error;
+use crate::BuildError;
+use crate::Config;
 use crate::Pack;
 use crate::Project;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+
+/// The minimum engine version which supports the manifest format version 3. Only this format allows SemVer
+/// strings for versions. The `generated_with` section in the metadata however **only** accepts strings.
+const FORMAT_V3_SINCE: semver::Version = semver::Version::new(1, 20, 80);
+
+/// A version in a manifest.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum Version {
+    /// The format `[majorVersion, minorVersion, revision]` which works with every format version.
+    Array([u64; 3]),
+
+    /// A SemVer string like `1.2.0-beta.3` which requires the format version 3.
+    String(String),
+}
+
+impl Version {
+    /// Converts `version` into the string form if `strings` is `true` or into the array form otherwise.
+    fn new(version: &semver::Version, strings: bool) -> Result<Self, BuildError> {
+        if strings {
+            return Ok(Self::String(version.to_string()));
+        }
+        if !version.pre.is_empty() || !version.build.is_empty() {
+            return Err(BuildError::InvalidVersion {
+                version: version.to_string(),
+                reason: format!(
+                    "pre-release versions require a `min-engine-version` of {} or higher",
+                    FORMAT_V3_SINCE
+                ),
+            });
+        }
+        Ok(Self::Array([version.major, version.minor, version.patch]))
+    }
+}
 
-// NOTE: The official documentation states that `version` keys may alternatively be SemVer strings but that
-//       does not seem to work. The format `[majorVersion, minorVersion, revision]` works however. The
-//       `generated_with` section in the metadata however **only** accepts strings (even though metadata
-//       appears to be ignored by Minecraft anyways).
-pub type Version = [u8; 3];
-
-fn version_from_string(s: &str) -> Version {
-    let version: Vec<u8> = s
-        .split(".")
-        .take(3)
-        .map(|x| x.parse::<u8>().expect("not a number")) // TODO: better error handling
-        .collect();
-    Version::try_from(version).expect("invalid version") // TODO: better error handling
+fn version_from_string(s: &str) -> Result<semver::Version, BuildError> {
+    semver::Version::parse(s).map_err(|e| BuildError::InvalidVersion {
+        version: s.to_string(),
+        reason: e.to_string(),
+    })
 }
 
 /// The manifest format used to "package" Minecraft add-ons.
@@ -53,7 +82,7 @@ pub struct Manifest {
 
     /// Section containing optional features that can be enabled in Minecraft.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub capabilities: Option<Capabilities>,
+    pub capabilities: Option<Vec<Capability>>,
 
     /// Section containing the metadata about the file such as authors and
     /// licensing information.
@@ -62,9 +91,56 @@ pub struct Manifest {
 }
 
 impl Manifest {
-    pub fn build(pack: Pack, project: Project) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn build(pack: Pack, project: Project) -> Result<Self, BuildError> {
+        let min_engine_version = version_from_string(&project.config.project.min_engine_version)?;
+        // skin packs and world templates do not support the format version 3
+        let strings = matches!(pack, Pack::Behavior | Pack::Resource)
+            && min_engine_version >= FORMAT_V3_SINCE;
+        let version = version_from_string(&project.config.project.version)?;
+        if matches!(pack, Pack::Skin | Pack::WorldTemplate)
+            && (!version.pre.is_empty() || !version.build.is_empty())
+        {
+            return Err(BuildError::InvalidVersion {
+                version: version.to_string(),
+                reason: "skin packs and world templates do not support pre-release versions"
+                    .to_string(),
+            });
+        }
+        let version = Version::new(&version, strings)?;
+        if pack == Pack::WorldTemplate {
+            check_base_game_version(&project.config.wt.base_game_version, &min_engine_version)?;
+        }
+        let uuids = project.uuids.of(&pack);
+        let modules = module_types(pack, &project.config)?
+            .into_iter()
+            .map(|kind| {
+                let module = Module::new(
+                    kind,
+                    uuids
+                        .module_of(pack, kind)
+                        .ok_or(BuildError::MissingUuid(pack))?,
+                    version.clone(),
+                );
+                Ok(match (kind, project.config.bp.script_settings()) {
+                    (ModuleType::Script, Some(script)) => Module {
+                        language: Some(Language::JavaScript),
+                        entry: Some(script.entry()),
+                        ..module
+                    },
+                    _ => module,
+                })
+            })
+            .collect::<Result<Vec<Module>, BuildError>>()?;
+        let capabilities: Vec<Capability> = capabilities(pack, &project.config)
+            .into_iter()
+            .filter(|capability| capability.supports(pack))
+            .collect();
         Ok(Manifest {
-            format_version: 2,
+            format_version: match pack {
+                Pack::Skin => 1,
+                _ if strings => 3,
+                _ => 2,
+            },
             header: Header {
                 allow_random_seed: match pack {
                     Pack::WorldTemplate => Some(project.config.wt.allow_random_seed),
@@ -76,69 +152,61 @@ impl Manifest {
                     None
                 },
                 description: String::from("pack.description"),
-                lock_template_options: None,
-                min_engine_version: Some(version_from_string(
-                    &project.config.project.min_engine_version,
-                )),
+                lock_template_options: match pack {
+                    Pack::WorldTemplate => Some(project.config.wt.lock_template_options),
+                    _ => None,
+                },
+                // world templates use `base_game_version` instead
+                min_engine_version: match pack {
+                    Pack::WorldTemplate => None,
+                    _ => Some(Version::new(&min_engine_version, false)?),
+                },
                 // min_engine_version: Some((1, 19, 0)),
                 name: String::from("pack.name"),
                 uuid: project
                     .uuids
                     .of(&pack)
                     .header
-                    .ok_or(Error::MissingUuid(pack))?
+                    .ok_or(BuildError::MissingUuid(pack))?
                     .to_string(),
-                version: version_from_string(&project.config.project.version),
+                version: version.clone(),
                 // version: (1, 0, 0),
             },
-            modules: Some(vec![Module {
-                description: String::from("pack.description"),
-                kind: match &pack {
-                    Pack::Behavior => project.config.bp.kind.into(),
-                    Pack::Resource => ModuleType::Resources,
-                    Pack::Skin => ModuleType::SkinPack,
-                    Pack::WorldTemplate => ModuleType::WorldTemplate,
-                },
-                language: match (pack, project.config.bp.kind) {
-                    (Pack::Behavior, BehaviorPackType::Script) => Some(Language::JavaScript),
-                    _ => None,
-                },
-                uuid: project
-                    .uuids
-                    .of(&pack)
-                    .module
-                    .ok_or(Error::MissingUuid(pack))?
-                    .to_string(),
-                version: version_from_string(&project.config.project.version),
-                // version: (1, 0, 0),
-            }]),
+            modules: Some(modules),
             dependencies: match pack {
                 Pack::Behavior => {
-                    let mut deps: Vec<Dependency> = Vec::new();
-                    deps.extend(project.config.bp.dependencies.iter().map(|dep| dep.into()));
+                    let mut deps: Vec<Dependency> = Dependency::from_config(
+                        &project.config.bp.dependencies,
+                        matches!(project.config.bp.kind, BehaviorPackType::Script),
+                        strings,
+                    )?;
                     if Pack::Resource.exists() {
-                        deps.push(Dependency {
+                        deps.push(Dependency::Pack {
                             uuid: project
                                 .uuids
                                 .rp
                                 .header
-                                .ok_or(Error::MissingUuid(Pack::Resource))?
+                                .ok_or(BuildError::MissingUuid(Pack::Resource))?
                                 .to_string(),
-                            version: version_from_string(&project.config.project.version),
+                            version,
                             // version: (1, 0, 0),
                         });
                     }
                     Some(deps)
                 }
                 Pack::Resource => {
-                    let mut deps: Vec<Dependency> = Vec::new();
-                    deps.extend(project.config.rp.dependencies.iter().map(|dep| dep.into()));
+                    let deps: Vec<Dependency> =
+                        Dependency::from_config(&project.config.rp.dependencies, false, strings)?;
                     Some(deps)
                 }
-                Pack::Skin => None,
+                Pack::Skin => {
+                    let deps: Vec<Dependency> =
+                        Dependency::from_config(&project.config.sp.dependencies, false, strings)?;
+                    (!deps.is_empty()).then_some(deps)
+                }
                 Pack::WorldTemplate => None,
             },
-            capabilities: project.config.capabilities, // FIXME: doesn't work; TODO: does this apply for all pack kinds?
+            capabilities: (!capabilities.is_empty()).then_some(capabilities),
             metadata: Some(Metadata {
                 authors: project.config.project.authors,
                 license: project.config.project.license,
@@ -156,6 +224,96 @@ impl Manifest {
     }
 }
 
+/// Ensures that the world template does not require an older version of the base game than the packs.
+fn check_base_game_version(
+    base_game_version: &BaseGameVersion,
+    min_engine_version: &semver::Version,
+) -> Result<(), BuildError> {
+    match base_game_version {
+        BaseGameVersion::Wild => Ok(()),
+        BaseGameVersion::Version(major, minor, patch) => {
+            let version = semver::Version::new(*major as u64, *minor as u64, *patch as u64);
+            if &version < min_engine_version {
+                Err(BuildError::InvalidVersion {
+                    version: version.to_string(),
+                    reason: format!(
+                        "`WT.base-game-version` must not be lower than \
+                         `project.min-engine-version` ({})",
+                        min_engine_version
+                    ),
+                })
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Returns the types of the modules of `pack` as defined in the configuration or the default ones.
+pub fn module_types(pack: Pack, config: &Config) -> Result<Vec<ModuleType>, BuildError> {
+    let scripting = matches!(config.bp.kind, BehaviorPackType::Script);
+    if pack == Pack::Behavior && !scripting && config.bp.script.is_some() {
+        return Err(BuildError::invalid_config(
+            "`[BP.script]` can only be used with `BP.type = \"script\"`".to_string(),
+        ));
+    }
+
+    let configured = match pack {
+        Pack::Behavior => &config.bp.modules,
+        Pack::Resource => &config.rp.modules,
+        Pack::Skin => &config.sp.modules,
+        Pack::WorldTemplate => &config.wt.modules,
+    };
+    let types = match (configured, config.bp.script_settings()) {
+        (Some(types), _) => types.clone(),
+        (None, Some(script)) if pack == Pack::Behavior => {
+            if script.data_module {
+                vec![ModuleType::Data, ModuleType::Script]
+            } else {
+                vec![ModuleType::Script]
+            }
+        }
+        (None, _) => vec![ModuleType::primary(pack)],
+    };
+
+    let section = pack.short_name();
+    if types.is_empty() {
+        return Err(BuildError::invalid_config(format!(
+            "`{}.modules` must not be empty",
+            section
+        )));
+    }
+    for (i, kind) in types.iter().enumerate() {
+        if types[..i].contains(kind) {
+            return Err(BuildError::invalid_config(format!(
+                "module `{}` is defined more than once in `{}.modules`",
+                kind.key(),
+                section
+            )));
+        }
+    }
+    // world templates and skin packs cannot be combined with other modules
+    if matches!(pack, Pack::WorldTemplate | Pack::Skin) && types != [ModuleType::primary(pack)] {
+        return Err(BuildError::invalid_config(format!(
+            "`{}.modules` must only contain `{}`",
+            section,
+            ModuleType::primary(pack).key()
+        )));
+    }
+    let has_script = types.contains(&ModuleType::Script);
+    if has_script && !(pack == Pack::Behavior && scripting) {
+        return Err(BuildError::invalid_config(
+            "`script` modules can only be used with `BP.type = \"script\"`".to_string(),
+        ));
+    }
+    if pack == Pack::Behavior && scripting && !has_script {
+        return Err(BuildError::invalid_config(
+            "`BP.modules` must contain a `script` module for `BP.type = \"script\"`".to_string(),
+        ));
+    }
+    Ok(types)
+}
+
 #[derive(Debug, Serialize)]
 pub struct Header {
     /// This option will generate a random seed every time a template is
@@ -191,6 +349,9 @@ pub struct Header {
     /// helps the game identify whether any backwards compatibility is needed
     /// for your pack. You should always use the highest version currently
     /// available when creating packs.
+    ///
+    /// [None] if a world pack.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub min_engine_version: Option<Version>,
 
     /// This is the name of the pack as it appears within Minecraft. This is
@@ -230,6 +391,11 @@ pub struct Module {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub language: Option<Language>,
 
+    /// Only present if `type` is `script`. This is the path of the script relative to the pack which is run
+    /// when the pack is loaded (e.g. `scripts/main.js`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry: Option<String>,
+
     /// This is a unique identifier for the module in the same format as the
     /// pack's UUID in the header. This should be different from the pack's
     /// UUID, and different for every module.
@@ -241,34 +407,78 @@ pub struct Module {
     pub version: Version,
 }
 
-/// A reference to an add-on by specifying its UUID and version.
-#[derive(Clone, Debug, Deserialize, Serialize)]
-#[cfg_attr(
-    feature = "config-schema",
-    derive(schemars::JsonSchema),
-    schemars(deny_unknown_fields)
-)]
-pub struct Dependency {
-    /// This is the unique identifier of the pack that this pack depends on.
-    /// It needs to be the exact same UUID that the pack has defined in the
-    /// header section of its manifest file.
-    pub uuid: String,
+impl Module {
+    fn new(kind: ModuleType, uuid: libuuid::Uuid, version: Version) -> Self {
+        Self {
+            description: String::from("pack.description"),
+            kind,
+            language: None,
+            entry: None,
+            uuid: uuid.to_string(),
+            version,
+        }
+    }
+}
 
-    /// This is the specific version of the pack that your pack depends on.
-    /// Should match the version the other pack has in its manifest file.
-    pub version: Version,
+/// A reference to another add-on or a script module.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum Dependency {
+    /// A reference to an add-on by specifying its UUID and version.
+    Pack {
+        /// This is the unique identifier of the pack that this pack depends on.
+        /// It needs to be the exact same UUID that the pack has defined in the
+        /// header section of its manifest file.
+        uuid: String,
+
+        /// This is the specific version of the pack that your pack depends on.
+        /// Should match the version the other pack has in its manifest file.
+        version: Version,
+    },
+
+    /// A reference to a script module provided by Minecraft such as `@minecraft/server`.
+    Module {
+        /// The name of the module (e.g. `@minecraft/server-ui`).
+        module_name: String,
+
+        /// The version of the module as a SemVer string (e.g. `1.8.0` or `1.9.0-beta`).
+        version: String,
+    },
 }
 
-impl From<&config::Dependency> for Dependency {
-    fn from(value: &config::Dependency) -> Self {
-        Self {
-            uuid: match &value.id {
-                config::Identifier::ModuleName(_) => todo!("module_name key; use UUID instead"),
-                config::Identifier::Uuid(id) => id.to_string(),
-            },
-            version: version_from_string(&value.version),
-            // version: (1, 0, 0),
-        }
+impl Dependency {
+    /// Converts the dependencies defined in the configuration.
+    ///
+    /// `scripting` defines whether the pack is allowed to depend on script modules and `strings` whether
+    /// versions of other packs are written as SemVer strings.
+    fn from_config(
+        deps: &[config::Dependency],
+        scripting: bool,
+        strings: bool,
+    ) -> Result<Vec<Self>, BuildError> {
+        deps.iter()
+            .map(|dep| match &dep.id {
+                config::Identifier::ModuleName(name) if !scripting => {
+                    Err(BuildError::InvalidDependency {
+                        dependency: name.clone(),
+                        reason: "script modules can only be used by behavior packs with \
+                                 `type = \"script\"`"
+                            .to_string(),
+                    })
+                }
+                // script modules always use SemVer strings
+                config::Identifier::ModuleName(name) => Ok(Self::Module {
+                    module_name: name.clone(),
+                    version: version_from_string(&dep.version)?.to_string(),
+                }),
+                config::Identifier::Uuid(id) => Ok(Self::Pack {
+                    uuid: id.to_string(),
+                    version: Version::new(&version_from_string(&dep.version)?, strings)?,
+                    // version: (1, 0, 0),
+                }),
+            })
+            .collect()
     }
 }
 
@@ -279,23 +489,19 @@ pub enum Language {
     JavaScript,
 }
 
-/// > **Warning**
-/// >
-/// > The type of the keys are nowhere documented. I'm assuming these are
-/// > boolean values.
-#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+/// Optional features of a pack which are enabled in the `[BP.capabilities]` or `[RP.capabilities]` section.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
 #[cfg_attr(
     feature = "config-schema",
     derive(schemars::JsonSchema),
     schemars(deny_unknown_fields)
 )]
-#[serde(rename_all(deserialize = "kebab-case"))]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
 pub struct Capabilities {
     /// The pack can add, remove, or modify chemistry behavior.
     pub chemistry: bool,
 
     /// Indicates that this pack contains extensions for editing.
-    #[serde(rename(serialize = "editorExtension"))]
     pub editor_extension: bool,
 
     /// The pack can use HTML files to create custom UI, as well as use
@@ -306,6 +512,86 @@ pub struct Capabilities {
     pub raytraced: bool,
 }
 
+impl Capabilities {
+    /// Enables the capabilities enabled in `other` which are supported by `pack`.
+    pub fn extend(&mut self, other: &Capabilities, pack: Pack) {
+        self.chemistry |= other.chemistry && Capability::Chemistry.supports(pack);
+        self.editor_extension |=
+            other.editor_extension && Capability::EditorExtension.supports(pack);
+        self.experimental_custom_ui |=
+            other.experimental_custom_ui && Capability::ExperimentalCustomUi.supports(pack);
+        self.raytraced |= other.raytraced && Capability::Raytraced.supports(pack);
+    }
+
+    /// Returns the enabled capabilities.
+    pub fn enabled(&self) -> Vec<Capability> {
+        [
+            (self.chemistry, Capability::Chemistry),
+            (self.editor_extension, Capability::EditorExtension),
+            (
+                self.experimental_custom_ui,
+                Capability::ExperimentalCustomUi,
+            ),
+            (self.raytraced, Capability::Raytraced),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, capability)| enabled.then_some(capability))
+        .collect()
+    }
+}
+
+/// A capability as written to the `capabilities` array of the manifest.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum Capability {
+    #[serde(rename = "chemistry")]
+    Chemistry,
+
+    #[serde(rename = "editorExtension")]
+    EditorExtension,
+
+    #[serde(rename = "experimental_custom_ui")]
+    ExperimentalCustomUi,
+
+    #[serde(rename = "raytraced")]
+    Raytraced,
+}
+
+impl Capability {
+    /// Returns whether the capability can be used by `pack`.
+    pub fn supports(self, pack: Pack) -> bool {
+        match self {
+            Self::Chemistry => matches!(pack, Pack::Behavior | Pack::Resource),
+            Self::EditorExtension => pack == Pack::Behavior,
+            Self::ExperimentalCustomUi | Self::Raytraced => pack == Pack::Resource,
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Chemistry => "chemistry",
+                Self::EditorExtension => "editorExtension",
+                Self::ExperimentalCustomUi => "experimental_custom_ui",
+                Self::Raytraced => "raytraced",
+            }
+        )
+    }
+}
+
+/// Returns the capabilities enabled for `pack` in the configuration including those which are not
+/// supported by the pack.
+pub fn capabilities(pack: Pack, config: &Config) -> Vec<Capability> {
+    match pack {
+        Pack::Behavior => config.bp.capabilities.enabled(),
+        Pack::Resource => config.rp.capabilities.enabled(),
+        Pack::Skin | Pack::WorldTemplate => Vec::new(),
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct Metadata {
     /// Name of the author(s) of the pack
@@ -333,8 +619,8 @@ pub struct Metadata {
     derive(schemars::JsonSchema),
     schemars(deny_unknown_fields)
 )]
-#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize)]
-#[serde(rename_all(serialize = "snake_case"))]
+#[derive(Copy, Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum ModuleType {
     Resources,
@@ -347,11 +633,41 @@ pub enum ModuleType {
     SkinPack,
 }
 
-impl From<BehaviorPackType> for ModuleType {
-    fn from(value: BehaviorPackType) -> Self {
-        match value {
-            BehaviorPackType::Data => Self::Data,
-            BehaviorPackType::Script => Self::Script,
+impl ModuleType {
+    pub const VALUES: [Self; 7] = [
+        Self::Resources,
+        Self::Data,
+        Self::ClientData,
+        Self::Interface,
+        Self::WorldTemplate,
+        Self::Script,
+        Self::SkinPack,
+    ];
+
+    /// Returns the name of the module type as used in manifests and `uuids.toml` (e.g. `client_data`).
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Resources => "resources",
+            Self::Data => "data",
+            Self::ClientData => "client_data",
+            Self::Interface => "interface",
+            Self::WorldTemplate => "world_template",
+            Self::Script => "script",
+            Self::SkinPack => "skin_pack",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::VALUES.into_iter().find(|kind| kind.key() == key)
+    }
+
+    /// Returns the module type every pack of the kind `pack` has by default.
+    pub fn primary(pack: Pack) -> Self {
+        match pack {
+            Pack::Behavior => Self::Data,
+            Pack::Resource => Self::Resources,
+            Pack::Skin => Self::SkinPack,
+            Pack::WorldTemplate => Self::WorldTemplate,
         }
     }
 }
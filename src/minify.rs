@@ -0,0 +1,126 @@
+//! Minification of the JSON files of packs built in release mode.
+//!
+//! Minecraft tolerates `//` and `/* */` comments in most JSON files. These are removed together with any
+//! insignificant whitespace. The order of object keys is preserved.
+
+use crate::BuildError;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File extension of the files that are minified.
+pub const JSON_FILE_EXTENSION: &str = "json";
+
+/// Summary of the minification of a directory.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Minified {
+    /// The number of minified files.
+    pub files: usize,
+
+    /// The number of bytes the files were reduced by.
+    pub saved: u64,
+}
+
+/// Replaces the comments of `json` with whitespace.
+///
+/// Line breaks within comments are kept so that locations in the returned string match those in `json`.
+pub fn strip_comments(json: &str) -> String {
+    let mut stripped = String::with_capacity(json.len());
+    let mut chars = json.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            stripped.push(c);
+            match c {
+                '\\' => {
+                    if let Some(escaped) = chars.next() {
+                        stripped.push(escaped);
+                    }
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                stripped.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        stripped.push(c);
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    if c == '\n' {
+                        stripped.push(c);
+                    }
+                    previous = c;
+                }
+            }
+            _ => stripped.push(c),
+        }
+    }
+    stripped
+}
+
+/// Removes the location parsers add to `message` as errors show it separately.
+pub(crate) fn strip_location(message: &str, line: usize, column: usize) -> String {
+    message.replace(&format!(" at line {} column {}", line, column), "")
+}
+
+/// Parses `json`, the content of the file at `path`, which may contain comments.
+pub fn parse(path: &Path, json: &str) -> Result<serde_json::Value, BuildError> {
+    let json = json.strip_prefix('\u{feff}').unwrap_or(json);
+    serde_json::from_str(&strip_comments(json)).map_err(|e| BuildError::InvalidJson {
+        path: path.to_path_buf(),
+        line: Some(e.line()),
+        column: Some(e.column()),
+        message: strip_location(&e.to_string(), e.line(), e.column()),
+    })
+}
+
+/// Like [`parse`] but returns the JSON minified.
+pub fn minify(path: &Path, json: &str) -> Result<String, BuildError> {
+    Ok(parse(path, json)?.to_string())
+}
+
+/// Minifies every JSON file in `dir` in place except for the files directly in `dir` named in `keep`.
+///
+/// `display_path` maps the path of a file to the one shown in errors.
+pub fn minify_dir<F>(dir: &Path, keep: &[&str], display_path: F) -> Result<Minified, BuildError>
+where
+    F: Fn(&Path) -> PathBuf,
+{
+    let mut minified = Minified::default();
+    for entry in walkdir::WalkDir::new(dir) {
+        let entry = entry.map_err(BuildError::walk(dir))?;
+        let path = entry.path();
+        let is_json = entry.file_type().is_file()
+            && path
+                .extension()
+                .is_some_and(|ext| ext == JSON_FILE_EXTENSION);
+        let kept = entry.depth() == 1
+            && keep
+                .iter()
+                .any(|name| entry.file_name() == std::ffi::OsStr::new(name));
+        if !is_json || kept {
+            continue;
+        }
+        let json = fs::read_to_string(path).map_err(BuildError::io(path))?;
+        let compact = minify(&display_path(path), &json)?;
+        fs::write(path, &compact).map_err(BuildError::io(path))?;
+        minified.files += 1;
+        minified.saved += json.len().saturating_sub(compact.len()) as u64;
+    }
+    Ok(minified)
+}
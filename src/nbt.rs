@@ -0,0 +1,467 @@
+//! Reading and writing of the little-endian [NBT][] format used by Minecraft: Bedrock Edition.
+//!
+//! Only what is needed to modify `level.dat` files is supported. These start with a header of two 32-bit
+//! integers containing the storage version and the length of the following compound tag.
+//!
+//! [NBT]: https://wiki.bedrock.dev/nbt/nbt-in-depth.html
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+const END: u8 = 0;
+const BYTE: u8 = 1;
+const SHORT: u8 = 2;
+const INT: u8 = 3;
+const LONG: u8 = 4;
+const FLOAT: u8 = 5;
+const DOUBLE: u8 = 6;
+const BYTE_ARRAY: u8 = 7;
+const STRING: u8 = 8;
+const LIST: u8 = 9;
+const COMPOUND: u8 = 10;
+const INT_ARRAY: u8 = 11;
+const LONG_ARRAY: u8 = 12;
+
+/// The maximum nesting of lists and compound tags.
+const MAX_DEPTH: usize = 512;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    /// A list of tags of the same type given by its ID which is only relevant when the list is empty.
+    List(u8, Vec<Tag>),
+    Compound(Compound),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+impl Tag {
+    /// Returns the ID of the type of the tag.
+    pub fn id(&self) -> u8 {
+        match self {
+            Self::Byte(_) => BYTE,
+            Self::Short(_) => SHORT,
+            Self::Int(_) => INT,
+            Self::Long(_) => LONG,
+            Self::Float(_) => FLOAT,
+            Self::Double(_) => DOUBLE,
+            Self::ByteArray(_) => BYTE_ARRAY,
+            Self::String(_) => STRING,
+            Self::List(..) => LIST,
+            Self::Compound(_) => COMPOUND,
+            Self::IntArray(_) => INT_ARRAY,
+            Self::LongArray(_) => LONG_ARRAY,
+        }
+    }
+}
+
+/// Named tags in the order they were read.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Compound(pub Vec<(String, Tag)>);
+
+impl Compound {
+    pub fn get(&self, name: &str) -> Option<&Tag> {
+        self.0.iter().find(|(n, _)| n == name).map(|(_, tag)| tag)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Tag> {
+        self.0
+            .iter_mut()
+            .find(|(n, _)| n == name)
+            .map(|(_, tag)| tag)
+    }
+
+    /// Sets the tag `name` to `tag` keeping its position if it already exists.
+    pub fn insert(&mut self, name: impl Into<String>, tag: Tag) {
+        let name = name.into();
+        match self.get_mut(&name) {
+            Some(existing) => *existing = tag,
+            None => self.0.push((name, tag)),
+        }
+    }
+}
+
+/// The content of a `level.dat` file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelDat {
+    /// The storage version from the header.
+    pub version: i32,
+
+    /// The name of the root tag which is usually empty.
+    pub name: String,
+
+    pub root: Compound,
+}
+
+impl LevelDat {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_bytes()?)
+    }
+
+    /// Parses the content of a `level.dat` file.
+    pub fn from_bytes(mut data: &[u8]) -> io::Result<Self> {
+        let reader = &mut data;
+        let version = read_i32(reader)?;
+        let length = read_i32(reader)?;
+        if usize::try_from(length).ok() != Some(reader.len()) {
+            return Err(invalid(format!(
+                "header announces {} bytes but {} bytes follow",
+                length,
+                reader.len()
+            )));
+        }
+        let (name, root) = read(reader)?;
+        if !reader.is_empty() {
+            return Err(invalid(format!(
+                "{} unexpected bytes after the root tag",
+                reader.len()
+            )));
+        }
+        Ok(Self {
+            version,
+            name,
+            root,
+        })
+    }
+
+    /// Returns the content of the `level.dat` file.
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        let mut payload = Vec::new();
+        write(&mut payload, &self.name, &self.root)?;
+        let mut data = Vec::with_capacity(payload.len() + 8);
+        data.write_all(&self.version.to_le_bytes())?;
+        write_len(&mut data, payload.len())?;
+        data.write_all(&payload)?;
+        Ok(data)
+    }
+}
+
+/// Reads a named compound tag from the beginning of `reader` and advances it past the tag.
+pub fn read(reader: &mut &[u8]) -> io::Result<(String, Compound)> {
+    let id = read_u8(reader)?;
+    if id != COMPOUND {
+        return Err(invalid(format!("expected compound tag, found tag {}", id)));
+    }
+    let name = read_string(reader)?;
+    Ok((name, read_compound(reader, 0)?))
+}
+
+/// Writes `compound` as a named compound tag.
+pub fn write(writer: &mut impl Write, name: &str, compound: &Compound) -> io::Result<()> {
+    writer.write_all(&[COMPOUND])?;
+    write_string(writer, name)?;
+    write_compound(writer, compound)
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_u8(reader: &mut &[u8]) -> io::Result<u8> {
+    let mut buf = [0; 1];
+    reader.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+fn read_i16(reader: &mut &[u8]) -> io::Result<i16> {
+    let mut buf = [0; 2];
+    reader.read_exact(&mut buf)?;
+    Ok(i16::from_le_bytes(buf))
+}
+
+fn read_i32(reader: &mut &[u8]) -> io::Result<i32> {
+    let mut buf = [0; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(i32::from_le_bytes(buf))
+}
+
+fn read_i64(reader: &mut &[u8]) -> io::Result<i64> {
+    let mut buf = [0; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(i64::from_le_bytes(buf))
+}
+
+/// Reads the length of an array or list whose elements take at least `size` bytes each and ensures that
+/// `reader` contains enough bytes for them.
+fn read_len(reader: &mut &[u8], size: usize) -> io::Result<usize> {
+    let len = read_i32(reader)?;
+    let len = usize::try_from(len).map_err(|_| invalid(format!("invalid length {}", len)))?;
+    if len
+        .checked_mul(size)
+        .is_none_or(|bytes| bytes > reader.len())
+    {
+        return Err(invalid(format!(
+            "length {} exceeds the remaining {} bytes",
+            len,
+            reader.len()
+        )));
+    }
+    Ok(len)
+}
+
+fn read_string(reader: &mut &[u8]) -> io::Result<String> {
+    let len = read_i16(reader)? as u16 as usize;
+    let mut buf = vec![0; len];
+    reader.read_exact(&mut buf)?;
+    String::from_utf8(buf).map_err(|e| invalid(e.to_string()))
+}
+
+fn read_compound(reader: &mut &[u8], depth: usize) -> io::Result<Compound> {
+    let mut compound = Compound::default();
+    loop {
+        let id = read_u8(reader)?;
+        if id == END {
+            return Ok(compound);
+        }
+        let name = read_string(reader)?;
+        let tag = read_payload(reader, id, depth + 1)?;
+        compound.0.push((name, tag));
+    }
+}
+
+fn read_payload(reader: &mut &[u8], id: u8, depth: usize) -> io::Result<Tag> {
+    if depth > MAX_DEPTH {
+        return Err(invalid(format!(
+            "tags are nested deeper than {}",
+            MAX_DEPTH
+        )));
+    }
+    Ok(match id {
+        BYTE => Tag::Byte(read_u8(reader)? as i8),
+        SHORT => Tag::Short(read_i16(reader)?),
+        INT => Tag::Int(read_i32(reader)?),
+        LONG => Tag::Long(read_i64(reader)?),
+        FLOAT => Tag::Float(f32::from_bits(read_i32(reader)? as u32)),
+        DOUBLE => Tag::Double(f64::from_bits(read_i64(reader)? as u64)),
+        BYTE_ARRAY => Tag::ByteArray(
+            (0..read_len(reader, 1)?)
+                .map(|_| read_u8(reader).map(|b| b as i8))
+                .collect::<io::Result<_>>()?,
+        ),
+        STRING => Tag::String(read_string(reader)?),
+        LIST => {
+            let kind = read_u8(reader)?;
+            // every item takes at least one byte
+            let items = (0..read_len(reader, 1)?)
+                .map(|_| read_payload(reader, kind, depth + 1))
+                .collect::<io::Result<_>>()?;
+            Tag::List(kind, items)
+        }
+        COMPOUND => Tag::Compound(read_compound(reader, depth)?),
+        INT_ARRAY => Tag::IntArray(
+            (0..read_len(reader, 4)?)
+                .map(|_| read_i32(reader))
+                .collect::<io::Result<_>>()?,
+        ),
+        LONG_ARRAY => Tag::LongArray(
+            (0..read_len(reader, 8)?)
+                .map(|_| read_i64(reader))
+                .collect::<io::Result<_>>()?,
+        ),
+        _ => return Err(invalid(format!("unknown tag {}", id))),
+    })
+}
+
+fn write_len(writer: &mut impl Write, len: usize) -> io::Result<()> {
+    let len = i32::try_from(len).map_err(|_| invalid(format!("invalid length {}", len)))?;
+    writer.write_all(&len.to_le_bytes())
+}
+
+fn write_string(writer: &mut impl Write, s: &str) -> io::Result<()> {
+    let len = u16::try_from(s.len()).map_err(|_| invalid(format!("string too long: {}", s)))?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(s.as_bytes())
+}
+
+fn write_compound(writer: &mut impl Write, compound: &Compound) -> io::Result<()> {
+    for (name, tag) in &compound.0 {
+        writer.write_all(&[tag.id()])?;
+        write_string(writer, name)?;
+        write_payload(writer, tag)?;
+    }
+    writer.write_all(&[END])
+}
+
+fn write_payload(writer: &mut impl Write, tag: &Tag) -> io::Result<()> {
+    match tag {
+        Tag::Byte(v) => writer.write_all(&v.to_le_bytes()),
+        Tag::Short(v) => writer.write_all(&v.to_le_bytes()),
+        Tag::Int(v) => writer.write_all(&v.to_le_bytes()),
+        Tag::Long(v) => writer.write_all(&v.to_le_bytes()),
+        Tag::Float(v) => writer.write_all(&v.to_le_bytes()),
+        Tag::Double(v) => writer.write_all(&v.to_le_bytes()),
+        Tag::ByteArray(values) => {
+            write_len(writer, values.len())?;
+            values
+                .iter()
+                .try_for_each(|v| writer.write_all(&v.to_le_bytes()))
+        }
+        Tag::String(s) => write_string(writer, s),
+        Tag::List(kind, items) => {
+            let kind = items.first().map(Tag::id).unwrap_or(*kind);
+            if items.iter().any(|item| item.id() != kind) {
+                return Err(invalid("list contains tags of different types".to_string()));
+            }
+            writer.write_all(&[kind])?;
+            write_len(writer, items.len())?;
+            items
+                .iter()
+                .try_for_each(|item| write_payload(writer, item))
+        }
+        Tag::Compound(compound) => write_compound(writer, compound),
+        Tag::IntArray(values) => {
+            write_len(writer, values.len())?;
+            values
+                .iter()
+                .try_for_each(|v| writer.write_all(&v.to_le_bytes()))
+        }
+        Tag::LongArray(values) => {
+            write_len(writer, values.len())?;
+            values
+                .iter()
+                .try_for_each(|v| writer.write_all(&v.to_le_bytes()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    /// A `level.dat` file of a flat world with experiments enabled.
+    const LEVEL_DAT: &[u8] = include_bytes!("../tests/fixtures/level.dat");
+
+    #[test]
+    fn load_and_save_level_dat() {
+        let path = env::temp_dir().join(format!("allay-nbt-{}.dat", std::process::id()));
+        fs::write(&path, LEVEL_DAT).unwrap();
+        let level = LevelDat::load(&path).unwrap();
+        level.save(&path).unwrap();
+        let saved = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(level.version, 10);
+        assert_eq!(
+            level.root.get("LevelName"),
+            Some(&Tag::String("Bedrock level".to_string()))
+        );
+        assert_eq!(saved, LEVEL_DAT);
+    }
+
+    #[test]
+    fn round_trip_every_tag() {
+        let root = Compound(vec![
+            ("byte".to_string(), Tag::Byte(-1)),
+            ("short".to_string(), Tag::Short(-300)),
+            ("int".to_string(), Tag::Int(70000)),
+            ("long".to_string(), Tag::Long(i64::MIN)),
+            ("float".to_string(), Tag::Float(0.05)),
+            ("double".to_string(), Tag::Double(-0.1)),
+            ("byte array".to_string(), Tag::ByteArray(vec![1, -2, 3])),
+            ("string".to_string(), Tag::String("Grüße".to_string())),
+            ("empty list".to_string(), Tag::List(COMPOUND, Vec::new())),
+            (
+                "list".to_string(),
+                Tag::List(INT, vec![Tag::Int(1), Tag::Int(21)]),
+            ),
+            (
+                "compound".to_string(),
+                Tag::Compound(Compound(vec![("nested".to_string(), Tag::Byte(1))])),
+            ),
+            ("int array".to_string(), Tag::IntArray(vec![i32::MAX, 0])),
+            ("long array".to_string(), Tag::LongArray(vec![i64::MAX])),
+        ]);
+        let level = LevelDat {
+            version: 10,
+            name: String::new(),
+            root,
+        };
+        let data = level.to_bytes().unwrap();
+        assert_eq!(&data[4..8], &(data.len() as i32 - 8).to_le_bytes());
+        assert_eq!(LevelDat::from_bytes(&data).unwrap(), level);
+    }
+
+    #[test]
+    fn truncated_level_dat() {
+        for len in 0..LEVEL_DAT.len() {
+            let mut data = LEVEL_DAT[..len].to_vec();
+            if len >= 8 {
+                // keep the header consistent so that the payload itself is truncated
+                data[4..8].copy_from_slice(&(len as i32 - 8).to_le_bytes());
+            }
+            assert!(LevelDat::from_bytes(&data).is_err(), "length {}", len);
+        }
+    }
+
+    #[test]
+    fn wrong_header_length() {
+        let mut data = LEVEL_DAT.to_vec();
+        data[4..8].copy_from_slice(&(LEVEL_DAT.len() as i32).to_le_bytes());
+        assert!(LevelDat::from_bytes(&data).is_err());
+    }
+
+    /// Returns a `level.dat` file containing a tag of type `id` named `a` with the given `payload`.
+    fn level_dat_with(id: u8, payload: &[u8]) -> Vec<u8> {
+        let mut tags = vec![COMPOUND, 0, 0, id, 1, 0, b'a'];
+        tags.extend_from_slice(payload);
+        tags.push(END);
+        let mut data = 10i32.to_le_bytes().to_vec();
+        data.extend_from_slice(&(tags.len() as i32).to_le_bytes());
+        data.extend_from_slice(&tags);
+        data
+    }
+
+    #[test]
+    fn corrupt_lengths() {
+        let huge = i32::MAX.to_le_bytes();
+        for (id, payload) in [
+            (BYTE_ARRAY, huge.to_vec()),
+            (INT_ARRAY, huge.to_vec()),
+            (LONG_ARRAY, huge.to_vec()),
+            (LIST, [&[INT][..], &huge].concat()),
+            (LIST, [&[COMPOUND][..], &huge].concat()),
+            (INT_ARRAY, (-1i32).to_le_bytes().to_vec()),
+            // two ints announced but only one follows
+            (INT_ARRAY, [&2i32.to_le_bytes()[..], &[0; 4]].concat()),
+        ] {
+            assert!(
+                LevelDat::from_bytes(&level_dat_with(id, &payload)).is_err(),
+                "tag {} with payload {:?}",
+                id,
+                payload
+            );
+        }
+    }
+
+    /// Returns the payload of a list containing a list `depth` times.
+    fn nested_lists(depth: usize) -> Vec<u8> {
+        let mut payload = Vec::new();
+        for _ in 0..depth {
+            payload.push(LIST);
+            payload.extend_from_slice(&1i32.to_le_bytes());
+        }
+        payload.push(BYTE);
+        payload.extend_from_slice(&1i32.to_le_bytes());
+        payload.push(0);
+        payload
+    }
+
+    #[test]
+    fn deeply_nested_lists() {
+        assert!(LevelDat::from_bytes(&level_dat_with(LIST, &nested_lists(MAX_DEPTH - 2))).is_ok());
+        assert!(LevelDat::from_bytes(&level_dat_with(LIST, &nested_lists(MAX_DEPTH))).is_err());
+    }
+}
@@ -1,13 +1,19 @@
 use crate::paths;
 use clap::builder::PossibleValue;
+use serde::Deserialize;
 use std::fmt;
 use std::path::PathBuf;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
 pub enum Pack {
+    #[serde(rename = "BP")]
     Behavior,
+    #[serde(rename = "RP")]
     Resource,
+    #[serde(rename = "SP")]
     Skin,
+    #[serde(rename = "WT")]
     WorldTemplate,
 }
 
@@ -37,6 +37,11 @@ pub fn global_internal() -> PathBuf {
         .join("allay")
 }
 
+/// Returns the path of the file containing the user-level targets of the `sync` command.
+pub fn sync_targets() -> PathBuf {
+    global_internal().join("sync.toml")
+}
+
 pub fn logs() -> PathBuf {
     global_internal().join("logs")
 }
@@ -49,9 +54,23 @@ pub fn version() -> PathBuf {
     internal().join("version.txt")
 }
 
-/// Returns the path of the built add-on.
-pub fn build() -> PathBuf {
-    PathBuf::from("build.mcaddon")
+/// Returns the path of the directory containing the build cache (`.allay/cache/`).
+pub fn cache() -> PathBuf {
+    internal().join("cache")
+}
+
+pub fn cache_file() -> PathBuf {
+    cache().join("cache.json")
+}
+
+/// Returns the path of the file recording the artifacts written by previous builds.
+pub fn artifacts() -> PathBuf {
+    cache().join("artifacts.json")
+}
+
+/// Returns the path of the directory containing the bundles of the most recent build.
+pub fn cache_bundles() -> PathBuf {
+    cache().join("bundles")
 }
 
 pub fn uuids() -> PathBuf {
@@ -89,3 +108,8 @@ pub fn pack_icon() -> PathBuf {
 pub fn gitignore() -> PathBuf {
     PathBuf::from(".gitignore")
 }
+
+/// Returns the path of the file listing the files which are not included in any pack (`.allayignore`).
+pub fn allayignore() -> PathBuf {
+    PathBuf::from(".allayignore")
+}
@@ -1,4 +1,5 @@
 use crate::config;
+use crate::BuildError;
 use std::ffi::OsStr;
 use std::process::{Command, Output};
 
@@ -6,7 +7,7 @@ pub trait Plugin {
     /// The optional name of the plugin.
     fn name(&self) -> Option<String>;
 
-    fn run<I, K, V>(&self, env_vars: I) -> Result<(String, Output), Box<dyn std::error::Error>>
+    fn run<I, K, V>(&self, env_vars: I) -> Result<(String, Output), BuildError>
     where
         I: IntoIterator<Item = (K, V)>,
         K: AsRef<OsStr>,
@@ -59,8 +60,7 @@ impl Plugin for ExecutablePlugin {
         self.name.clone()
     }
 
-    #[must_use]
-    fn run<I, K, V>(&self, env_vars: I) -> Result<(String, Output), Box<dyn std::error::Error>>
+    fn run<I, K, V>(&self, env_vars: I) -> Result<(String, Output), BuildError>
     where
         I: IntoIterator<Item = (K, V)>,
         K: AsRef<OsStr>,
@@ -68,8 +68,11 @@ impl Plugin for ExecutablePlugin {
     {
         let mut cmd = Command::new(&self.program);
         let cmd = cmd.args(&self.args).envs(env_vars);
-        let output = cmd.output()?;
         let name = self.name().unwrap_or("<unnamed>".to_string());
+        let output = cmd.output().map_err(|e| BuildError::PluginFailed {
+            name: name.clone(),
+            reason: format!("cannot run `{}`: {}", self.program, e),
+        })?;
         Ok((name, output))
     }
 
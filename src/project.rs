@@ -1,21 +1,33 @@
+use crate::cache::{self, Cache, FileHashes, PackState};
+use crate::config;
+use crate::diagnostic::{self, Diagnostic};
+use crate::exclude::Filter;
 use crate::health::has_content;
 use crate::localization::{
     collect_user_translations, generate_language_json, update_language_files, Localized,
-    OptionallyLocalized,
 };
+use crate::manifest;
+use crate::minify;
 use crate::plugin::{ExecutablePlugin, Plugin};
+use crate::report::{PackReport, PluginReport, Report};
+use crate::skins::{self, SkinPack};
+use crate::transpile;
+use crate::typescript;
+use crate::uuid;
+use crate::BuildError;
 use crate::Config;
-use crate::Error;
 use crate::Manifest;
 use crate::Pack;
-use crate::{diagnostic, uuid};
 use crate::{filter, paths, scaffolding, Health};
 use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str;
+use std::thread;
+use std::time::Instant;
 use zip_extensions::write::zip_create_from_directory;
 
 #[derive(Clone, Copy, Debug)]
@@ -38,6 +50,9 @@ pub struct Project {
 
     /// The unique ID of the project.
     pub id: libuuid::Uuid,
+
+    /// The name of the build profile applied to the configuration.
+    pub profile: String,
 }
 
 impl Project {
@@ -78,273 +93,401 @@ impl Project {
             fs::write(dir.join(paths::gitignore()), scaffolding::GITIGNORE)?;
         }
 
+        let config = Config::from_str(
+            str::from_utf8(scaffolding::CONFIG)
+                .expect("Config template is not UTF-8; please report this error"),
+        )
+        .expect("Config template is invalid; please report this error");
         Ok(Self {
-            config: Config::from_str(
-                str::from_utf8(scaffolding::CONFIG)
-                    .expect("Config template is not UTF-8; please report this error"),
-            )
-            .expect("Config template is invalid; please report this error"),
+            profile: default_profile(&config).to_string(),
+            config,
             uuids,
             id,
         })
     }
 
-    pub fn from_root(root_dir: &PathBuf) -> Result<Self, Box<dyn std::error::Error>> {
-        let cfg = fs::read_to_string(root_dir.join(paths::config()))?;
+    pub fn from_root(root_dir: &Path) -> Result<Self, BuildError> {
+        let read = |path: &Path| fs::read_to_string(path).map_err(BuildError::io(path));
+
+        let config_path = root_dir.join(paths::config());
+        let cfg = read(&config_path)?;
+        let mut config = Config::from_str(&cfg)
+            .map_err(|e| BuildError::config(&config_path, &cfg, e.span(), e.message()))?;
+        if config.migrate_capabilities() {
+            log::warn!(
+                "The `[capabilities]` section is deprecated; move the capabilities to `[BP.capabilities]` \
+                 and `[RP.capabilities]`"
+            );
+        }
+
+        let uuids_path = root_dir.join(paths::uuids());
+        let uuids = read(&uuids_path)?;
+        let uuids = uuid::Uuids::from_str(&uuids)
+            .map_err(|e| BuildError::config(&uuids_path, &uuids, e.span(), e.message()))?;
+
+        let id_path = root_dir.join(paths::project_id());
+        let id = read(&id_path)?;
+        let id = libuuid::Uuid::parse_str(&id)
+            .map_err(|e| BuildError::config(&id_path, &id, None, e))?;
+
         Ok(Self {
-            config: Config::from_str(&cfg)?,
-            uuids: uuid::Uuids::from_str(&fs::read_to_string(root_dir.join(paths::uuids()))?)?,
-            id: libuuid::Uuid::parse_str(&fs::read_to_string(root_dir.join(paths::project_id()))?)?,
+            profile: default_profile(&config).to_string(),
+            config,
+            uuids,
+            id,
         })
     }
 
     /// Returns the project by accessing the user's current working directory.
-    pub fn current() -> Result<Self, Box<dyn std::error::Error>> {
-        Self::from_root(&paths::try_root().ok_or(Error::NotInAProject)?)
+    pub fn current() -> Result<Self, BuildError> {
+        Self::from_root(&paths::try_root().ok_or(BuildError::NotInAProject)?)
     }
 
-    pub fn build(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    /// Applies the overrides of the profile `name` to the configuration.
+    pub fn apply_profile(&mut self, name: &str) -> Result<(), BuildError> {
+        let profile = match self.config.profile.get(name) {
+            Some(profile) => profile.clone(),
+            None if name == config::DEV_PROFILE || name == config::RELEASE_PROFILE => {
+                config::Profile::default()
+            }
+            None => {
+                return Err(BuildError::invalid_config(format!(
+                    "profile `{}` is not defined",
+                    name
+                )))
+            }
+        };
+
+        self.config.debug = profile.debug.unwrap_or(match name {
+            config::DEV_PROFILE => true,
+            config::RELEASE_PROFILE => false,
+            _ => self.config.debug,
+        });
+        self.config.env.extend(profile.env);
+        if let Some(plugins) = profile.plugins {
+            for plugin in &plugins {
+                if !self
+                    .config
+                    .plugin
+                    .iter()
+                    .any(|p| p.name.as_ref() == Some(plugin))
+                {
+                    return Err(BuildError::invalid_config(format!(
+                        "profile `{}` references undefined plugin `{}`",
+                        name, plugin
+                    )));
+                }
+            }
+            self.config
+                .plugin
+                .retain(|p| p.name.as_ref().is_some_and(|n| plugins.contains(n)));
+        }
+        if profile.pretty.is_some() {
+            self.config.build.pretty = profile.pretty;
+        }
+        if let Some(output) = profile.output {
+            self.config.build.output = output;
+        }
+        if let Some(addon_name) = profile.addon_name {
+            self.config.build.addon_name = addon_name;
+        }
+        if let Some(pack_name) = profile.pack_name {
+            self.config.build.pack_name = pack_name;
+        }
+        if let Some(version) = profile.version {
+            self.config.project.version = version;
+        }
+        self.profile = name.to_string();
+        Ok(())
+    }
+
+    /// Returns the file name of the bundle of `pack` (e.g. `My-Project-1.0.0-BP.mcpack`) or of the add-on
+    /// when `pack` is [`None`] (e.g. `My-Project-1.0.0.mcaddon`) according to the templates in the
+    /// `[build]` section.
+    pub fn artifact_name(&self, pack: Option<Pack>) -> Result<String, BuildError> {
+        let primary_language = &self.config.localization.primary_language;
+        let name = self.config.project.name.to_localized(primary_language);
+        let name = name
+            .get(primary_language)
+            .or_else(|| name.values().next())
+            .map(String::as_str)
+            .unwrap_or_default();
+
+        let (field, template) = match pack {
+            Some(_) => ("pack-name", &self.config.build.pack_name),
+            None => ("addon-name", &self.config.build.addon_name),
+        };
+        let invalid = |message: String| {
+            BuildError::invalid_config(format!("invalid `build.{}`: {}", field, message))
+        };
+        let mut rendered = String::new();
+        let mut rest = template.as_str();
+        while let Some(start) = rest.find('{') {
+            rendered.push_str(&rest[..start]);
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| invalid("unclosed `{`".to_string()))?;
+            let placeholder = &rest[start + 1..start + end];
+            match (placeholder, pack) {
+                ("name", _) => rendered.push_str(name),
+                ("version", _) => rendered.push_str(&self.config.project.version),
+                ("profile", _) => rendered.push_str(&self.profile),
+                ("pack", Some(pack)) => rendered.push_str(pack.short_name()),
+                _ => {
+                    return Err(invalid(format!(
+                        "unknown placeholder `{{{}}}`",
+                        placeholder
+                    )))
+                }
+            }
+            rest = &rest[start + end + 1..];
+        }
+        rendered.push_str(rest);
+
+        // keep the name usable as a file name on every platform
+        let rendered: String = rendered
+            .trim()
+            .chars()
+            .filter(|c| !matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|'))
+            .map(|c| if c.is_whitespace() { '-' } else { c })
+            .collect();
+        if rendered.is_empty() {
+            return Err(invalid("the file name is empty".to_string()));
+        }
+        Ok(format!(
+            "{}.{}",
+            rendered,
+            pack.map_or("mcaddon", |pack| pack.bundle_file_extension())
+        ))
+    }
+
+    /// Returns the files of `pack` that are included in the pack for the project located at `root`.
+    pub fn filter(&self, root: &Path, pack: Pack) -> Result<Filter, BuildError> {
+        let (include, exclude) = self.config.patterns(pack);
+        Filter::new(root, pack, include, exclude)
+    }
+
+    /// Returns the path of the bundle of `pack` or of the add-on when `pack` is [`None`] in the output
+    /// directory of the project located at `root`.
+    pub fn artifact(&self, root: &Path, pack: Option<Pack>) -> Result<PathBuf, BuildError> {
+        Ok(root
+            .join(&self.config.build.output)
+            .join(self.artifact_name(pack)?))
+    }
+
+    pub fn build(&mut self) -> Result<Report, BuildError> {
+        let mut report = Report::new(self.config.debug, &self.profile);
+        self.build_with_report(&mut report)?;
+        Ok(report)
+    }
+
+    /// Builds the project like [`Project::build`] and adds the outcome of the build to `report`.
+    ///
+    /// When the build fails, `report` contains everything that happened before the error.
+    pub fn build_with_report(&mut self, report: &mut Report) -> Result<(), BuildError> {
+        let root = paths::root();
         let health = Health {
-            root: paths::root(),
+            root: root.clone(),
             fix: false,
         };
         if !health.check_all_except_uuids() {
-            return Err(Box::new(Error::InvalidProjectSetup));
+            return Err(BuildError::InvalidProjectSetup);
         };
 
         let prebuild: PathBuf = env::temp_dir().join(format!("allay-{}", self.id));
         if prebuild.exists() {
             log::debug!("Delete old prebuild directory in {}", &prebuild.display());
-            fs::remove_dir_all(&prebuild)?;
+            fs::remove_dir_all(&prebuild).map_err(BuildError::io(&prebuild))?;
         }
         log::debug!("Create prebuild directory in {}", &prebuild.display());
-        fs::create_dir_all(&prebuild)?;
+        fs::create_dir_all(&prebuild).map_err(BuildError::io(&prebuild))?;
 
-        let mut packs: Vec<(Pack, PathBuf)> = Vec::new();
-        let copy_options = fs_extra::dir::CopyOptions::new().copy_inside(true);
-        for pack in Pack::VALUES {
-            if !pack.exists() {
-                continue;
-            }
+        let packs: Vec<Pack> = Pack::VALUES.into_iter().filter(Pack::exists).collect();
+        if packs.is_empty() {
+            report.notify(None, diagnostic::Notification::EmptyAddOn);
+            return Ok(());
+        }
 
+        let mut generated_uuids = false;
+        for pack in &packs {
             log::debug!("Generating UUIDs for {} if neccessary", pack);
-            match pack {
-                Pack::Behavior => self.uuids.bp.update_header(None).update_module(None),
-                Pack::Resource => self.uuids.rp.update_header(None).update_module(None),
-                Pack::Skin => self.uuids.sp.update_header(None).update_module(None),
-                Pack::WorldTemplate => self.uuids.wt.update_header(None).update_module(None),
-            };
-
-            let dest = prebuild.join(pack.short_name());
-            log::debug!("Copy {} source to {}", pack, dest.display());
-            fs_extra::dir::copy(
-                pack.path_src().ok_or(Error::NotInAProject)?,
-                &dest,
-                &copy_options,
-            )?;
-
-            log::debug!("Adding fingerprint");
-            if let Err(e) = fs::write(dest.join(paths::FINGERPRINT), self.id) {
-                log::error!("Failed to add fingerprint: {}", e);
-            };
-
-            let generate_manifest = !match pack {
-                Pack::Behavior => self.config.bp.custom_manifest,
-                Pack::Resource => self.config.rp.custom_manifest,
-                Pack::Skin => self.config.sp.custom_manifest,
-                Pack::WorldTemplate => self.config.wt.custom_manifest,
-            };
-            if generate_manifest {
-                log::debug!("Generating manifests");
-                let mf = Manifest::build(pack, self.clone())?;
-                let json = if self.config.debug {
-                    serde_json::to_string_pretty(&mf)?
-                } else {
-                    serde_json::to_string(&mf)?
-                };
-                let p = dest.join("manifest.json");
-                if p.try_exists().unwrap_or(false) && p.is_file() {
-                    log::warn!("{}", diagnostic::Notification::RedundantManifest);
-                };
-                fs::write(&p, json)?;
+            let data = self.uuids.of_mut(pack);
+            if data.header.is_none() {
+                data.update_header(None);
+                generated_uuids = true;
             }
-
-            let copy_pack_icon = !match pack {
-                Pack::Behavior => self.config.bp.custom_pack_icon,
-                Pack::Resource => self.config.rp.custom_pack_icon,
-                Pack::Skin => self.config.sp.custom_pack_icon,
-                Pack::WorldTemplate => false,
-            };
-            if copy_pack_icon {
-                log::debug!("Copying pack icon");
-                let p = dest.join("pack_icon.png");
-                if p.try_exists().unwrap_or(false) && p.is_file() {
-                    log::warn!("{}", diagnostic::Notification::RedundantPackIcon);
-                };
-                fs::copy(paths::pack_icon(), dest.join("pack_icon.png"))?;
+            if data.module.is_none() {
+                data.update_module(None);
+                generated_uuids = true;
             }
-
-            log::debug!("Generating/extending language files");
-            {
-                let mut groups = self.config.localization.groups.clone();
-                let name: Localized<String> = match &self.config.project.name {
-                    OptionallyLocalized::Localized(m) => m.clone(),
-                    OptionallyLocalized::Unlocalized(s) => {
-                        let mut map = HashMap::new();
-                        map.insert(
-                            self.config.localization.primary_language.clone(),
-                            s.to_string(),
-                        );
-                        map
-                    }
-                };
-                for l in name.keys() {
-                    groups.with_language(l.clone());
+            for kind in manifest::module_types(*pack, &self.config).map_err(|e| e.in_pack(*pack))? {
+                if data.module_of(*pack, kind).is_none() {
+                    data.update_module_of(*pack, kind, None);
+                    generated_uuids = true;
                 }
+            }
+        }
+        if generated_uuids {
+            log::debug!("Saving generated UUIDs");
+            let path = root.join(paths::uuids());
+            fs::write(&path, self.uuids.to_string()).map_err(BuildError::io(&path))?;
+        }
 
-                let desc: Localized<String> = match &self.config.project.description {
-                    OptionallyLocalized::Localized(m) => m.clone(),
-                    OptionallyLocalized::Unlocalized(s) => {
-                        let mut map = HashMap::new();
-                        map.insert(
-                            self.config.localization.primary_language.clone(),
-                            s.to_string(),
-                        );
-                        map
-                    }
-                };
-                for l in desc.keys() {
-                    groups.with_language(l.clone());
-                }
+        let plugins = self.plugins()?;
 
-                let mut translations: HashMap<String, Localized<String>> = HashMap::new();
-                translations.insert("pack.name".to_string(), name);
-                translations.insert("pack.description".to_string(), desc);
+        let mut cache = if self.config.build.incremental {
+            Cache::load(&root)
+        } else {
+            log::debug!("Ignoring build cache");
+            Cache::default()
+        };
+        let inputs = self.inputs_hash(&root, &plugins)?;
+        let mut sources: HashMap<Pack, FileHashes> = HashMap::new();
+        for pack in &packs {
+            log::debug!("Hashing source files of {}", pack);
+            let src = pack.path_src().ok_or(BuildError::NotInAProject)?;
+            let files = self
+                .filter(&root, *pack)?
+                .files()
+                .map_err(BuildError::io(&src))?;
+            sources.insert(
+                *pack,
+                cache::hash_files(&src, &files).map_err(BuildError::io(&src))?,
+            );
+        }
 
-                let texts_dir = dest.join("texts");
-                {
-                    let res = fs::create_dir(&texts_dir);
-                    if res
-                        .as_ref()
-                        .is_err_and(|e| e.kind() != io::ErrorKind::AlreadyExists)
-                    {
-                        res?;
-                    };
-                };
+        let includes = |wt_part: Pack| match wt_part {
+            Pack::Behavior => !self.config.wt.exclude_bp,
+            Pack::Resource => !self.config.wt.exclude_rp,
+            _ => false,
+        };
+        let mut stale: Vec<Pack> = packs
+            .iter()
+            .copied()
+            .filter(|pack| !cache.is_fresh(&root, *pack, &inputs, &sources[pack]))
+            .collect();
+        if packs.contains(&Pack::Behavior)
+            && !stale.contains(&Pack::Behavior)
+            && self.config.bp.script_settings().is_some()
+            && sources[&Pack::Behavior]
+                .keys()
+                .any(|path| typescript::is_source(Path::new(path)))
+        {
+            // the compiled scripts depend on files outside of the pack (e.g. `tsconfig.json`, the compiler
+            // and imported modules) which are not tracked
+            log::debug!(
+                "Rebuilding {} as it contains TypeScript files",
+                Pack::Behavior
+            );
+            stale.push(Pack::Behavior);
+        }
+        if !plugins.is_empty() && !self.config.build.cache_plugins {
+            // plugins may depend on files that are not tracked (e.g. modules imported by their scripts)
+            log::debug!("Rebuilding every pack as plugins are used");
+            stale.clone_from(&packs);
+        }
+        if packs.contains(&Pack::WorldTemplate)
+            && !stale.contains(&Pack::WorldTemplate)
+            && stale.iter().any(|pack| includes(*pack))
+        {
+            // the world template contains copies of the behavior and the resource pack
+            stale.push(Pack::WorldTemplate);
+        }
 
-                match collect_user_translations(&texts_dir) {
-                    Ok(user_translations) => {
-                        for (key, translation) in user_translations {
-                            translations.insert(key, translation);
-                        }
-                    }
-                    Err(e) => {
-                        log::error!("Error while collecting user-defined translations: {}", e)
+        // Plugins may read or modify any pack so they need to see all of them.
+        let prepare: Vec<Pack> = if stale.is_empty() {
+            log::info!("No changes since the previous build; reusing cached bundles");
+            Vec::new()
+        } else if !plugins.is_empty() {
+            packs.clone()
+        } else {
+            packs
+                .iter()
+                .copied()
+                .filter(|pack| {
+                    stale.contains(pack)
+                        || (stale.contains(&Pack::WorldTemplate) && includes(*pack))
+                })
+                .collect()
+        };
+        for pack in &packs {
+            if !prepare.contains(pack) {
+                log::info!("Reusing cached bundle of {}", pack);
+                // notifications are only raised while preparing a pack
+                for code in cache
+                    .packs
+                    .get(pack.short_name())
+                    .map(|state| state.notifications.as_slice())
+                    .unwrap_or_default()
+                {
+                    if let Some(notification) = diagnostic::Notification::from_code(*code) {
+                        report.notify(Some(*pack), notification);
                     }
                 }
+            }
+        }
 
-                match update_language_files(
-                    &texts_dir,
-                    &groups,
-                    &self.config.localization.primary_language,
-                    translations,
-                ) {
-                    Ok(_) => log::info!("Updates language files"),
-                    Err(e) => log::error!("Error while appending language files: {}", e),
-                };
-                match generate_language_json(&texts_dir) {
-                    Ok(_) => log::info!("Generated languages.json"),
-                    Err(e) => log::error!("Error while generating languages.json: {}", e),
-                };
-            };
+        in_parallel(&prepare, report, |pack, log| {
+            self.prepare_pack(pack, &prebuild.join(pack.short_name()), log)
+        })?;
 
-            packs.push((pack, dest));
+        if !prepare.is_empty() {
+            log::debug!("Run plugins");
+            self.run_plugins(&plugins, &prebuild, report)?;
         }
 
-        if packs.is_empty() {
-            log::warn!("{}", diagnostic::Notification::EmptyAddOn);
-            return Ok(());
+        if prepare.contains(&Pack::Behavior) {
+            if let Some(script) = self.config.bp.script_settings() {
+                // the entry may be generated by a plugin
+                let entry = script.entry();
+                if !prebuild.join("BP").join(&entry).is_file() {
+                    return Err(BuildError::MissingScriptEntry(entry).in_pack(Pack::Behavior));
+                }
+            }
         }
 
-        log::debug!("Run plugins");
-        {
-            for plugin in &self.config.plugin {
-                if let Some(when) = &plugin.when {
-                    match filter::evaluate(when) {
-                        Ok(false) => {
-                            log::info!(
-                                "skipping running plugin {} because filter evaluated false",
-                                plugin.name.as_ref().unwrap_or(&"<unnamed>".to_string()) // TODO: change `<unnamed>`
-                            );
-                            continue;
-                        }
-                        Ok(true) => {}
-                        Err(e) => {
-                            log::error!("Filter error\n{}", e);
-                            return Err(Box::new(e));
-                        }
-                    }
-                };
-                let name = plugin
-                    .name
-                    .clone()
-                    .unwrap_or("<unnamed plugin>".to_string());
-                let plugin = ExecutablePlugin::from(plugin);
-                let mut envs = Vec::new();
-                envs.push((
-                    "ALLAY_DEBUG",
-                    if self.config.debug {
-                        "1".into()
-                    } else {
-                        "0".into()
-                    },
+        if prepare.contains(&Pack::Skin) {
+            // the textures may be generated by a plugin
+            skins::check_textures(&prebuild.join("SP")).map_err(|e| e.in_pack(Pack::Skin))?;
+        }
+
+        if !self.config.debug {
+            log::debug!("Minifying JSON files");
+            // the generated files are already written in the requested format
+            let keep: &[&str] = if self.config.pretty() {
+                &["manifest.json", skins::SKINS_JSON]
+            } else {
+                &[]
+            };
+            in_parallel(&prepare, report, |pack, log| {
+                let dir = prebuild.join(pack.short_name());
+                let src = root.join(paths::src()).join(pack.short_name());
+                // point to the source file unless the file was generated by a plugin
+                let minified = minify::minify_dir(&dir, keep, |path| {
+                    path.strip_prefix(&dir)
+                        .map(|relative| src.join(relative))
+                        .ok()
+                        .filter(|path| path.is_file())
+                        .unwrap_or_else(|| path.to_path_buf())
+                })?;
+                log.info(format!(
+                    "Minified {} JSON files saving {} bytes",
+                    minified.files, minified.saved
                 ));
-                envs.push(("ALLAY_PREBUILD", prebuild.clone().into_os_string()));
-                envs.push(("ALLAY_PROJECT_ROOT", paths::root().into_os_string()));
-                envs.push(("ALLAY_VERSION", clap::crate_version!().into()));
-                envs.extend(
-                    self.config
-                        .env
-                        .iter()
-                        .map(|(key, value)| (key.as_str(), value.into())),
-                );
-                let result = plugin.run(envs);
-                match result {
-                    Ok((name, output)) => {
-                        let stdout = String::from_utf8_lossy(output.stdout.as_slice());
-                        let stderr = String::from_utf8_lossy(output.stderr.as_slice());
-                        for line in stdout.lines() {
-                            log::info!("[stdout of {}] {}", name, line);
-                        }
-                        for line in stderr.lines() {
-                            log::error!("[stderr of {}] {}", name, line);
-                        }
-                        if output.status.success() {
-                            log::info!("Plugin {} ran successfully", name);
-                        } else {
-                            log::error!("Plugin {} ran unsuccessfully", name);
-                        }
-                    }
-                    Err(e) => {
-                        log::error!("Failed to run plugin {}: {}", name, e);
-                        if plugin.panic() {
-                            return Err(e);
-                        }
-                    }
-                };
-            }
+                Ok(())
+            })?;
         }
 
-        {
+        if prepare.contains(&Pack::WorldTemplate) {
+            let copy_options = fs_extra::dir::CopyOptions::new().copy_inside(true);
             let rp = prebuild.join("RP");
             let bp = prebuild.join("BP");
             let wt = prebuild.join("WT");
 
             let has_rp = has_content(&Pack::Resource.path_src().unwrap());
             let has_bp = has_content(&Pack::Behavior.path_src().unwrap());
-            let has_wt = has_content(&Pack::WorldTemplate.path_src().unwrap());
 
-            if has_wt && !self.config.wt.exclude_bp && has_bp {
+            if !self.config.wt.exclude_bp && has_bp {
                 log::debug!("Copying behavior pack to world template");
 
                 let dest = wt.join("behavior_packs");
@@ -369,7 +512,7 @@ impl Project {
                 }
             }
 
-            if has_wt && !self.config.wt.exclude_rp && has_rp {
+            if !self.config.wt.exclude_rp && has_rp {
                 log::debug!("Copying resource pack to world template");
 
                 let dest = wt.join("resource_packs");
@@ -396,26 +539,74 @@ impl Project {
 
         log::debug!("Zipping add-ons");
         {
-            let mut bundles: Vec<PathBuf> = Vec::new();
-            for (pack, path) in packs {
-                let bundle = prebuild.join(format!(
-                    "{}.{}",
-                    pack.short_name(),
-                    pack.bundle_file_extension()
-                ));
-                // NOTE: zipping seems to be optional
-                zip_create_from_directory(&bundle, &path)?;
-                bundles.push(bundle);
+            let bundles = root.join(paths::cache_bundles());
+            fs::create_dir_all(&bundles).map_err(BuildError::io(&bundles))?;
+            let outputs = in_parallel(&prepare, report, |pack, log| {
+                let path = prebuild.join(pack.short_name());
+                let output = cache::digest(&cache::hash_dir(&path).map_err(BuildError::io(&path))?);
+                let zipped = !cache.has_output(&root, pack, &output);
+                if zipped {
+                    log.debug("Zipping pack");
+                    // NOTE: zipping seems to be optional
+                    let bundle = cache::bundle(&root, pack);
+                    zip_create_from_directory(&bundle, &path).map_err(BuildError::zip(&bundle))?;
+                } else {
+                    log.info("Pack did not change; reusing cached bundle");
+                }
+                Ok((output, zipped))
+            })?;
+            let mut zipped_packs = Vec::new();
+            for (pack, (output, zipped)) in outputs {
+                if zipped {
+                    zipped_packs.push(pack);
+                }
+                cache.packs.insert(
+                    pack.short_name().to_string(),
+                    PackState {
+                        files: sources.remove(&pack).unwrap_or_default(),
+                        output,
+                        notifications: report
+                            .notifications
+                            .iter()
+                            .filter(|n| n.pack.as_deref() == Some(pack.short_name()))
+                            .map(|n| n.code)
+                            .collect(),
+                    },
+                );
             }
 
-            // remove all entries in prebuild excpect bundles
-            for entry in prebuild.read_dir()? {
-                let entry = entry?.path();
-                if !bundles.contains(&entry) {
-                    if entry.is_file() {
-                        fs::remove_file(entry)?;
+            for pack in &packs {
+                let bundle = cache::bundle(&root, *pack);
+                let generate_manifest = !match pack {
+                    Pack::Behavior => self.config.bp.custom_manifest,
+                    Pack::Resource => self.config.rp.custom_manifest,
+                    Pack::Skin => self.config.sp.custom_manifest,
+                    Pack::WorldTemplate => self.config.wt.custom_manifest,
+                };
+                report.packs.push(PackReport {
+                    pack: pack.short_name().to_string(),
+                    size: fs::metadata(&bundle)
+                        .map_err(BuildError::io(&bundle))?
+                        .len(),
+                    cache_path: bundle,
+                    cached: !zipped_packs.contains(pack),
+                    manifest: if generate_manifest {
+                        let mf =
+                            Manifest::build(*pack, self.clone()).map_err(|e| e.in_pack(*pack))?;
+                        Some((&mf).into())
                     } else {
-                        fs::remove_dir_all(entry)?;
+                        None
+                    },
+                });
+            }
+
+            // forget about packs which no longer exist
+            for pack in Pack::VALUES {
+                if !packs.contains(&pack) {
+                    cache.packs.remove(pack.short_name());
+                    let bundle = cache::bundle(&root, pack);
+                    if bundle.exists() {
+                        fs::remove_file(&bundle).map_err(BuildError::io(&bundle))?;
                     }
                 }
             }
@@ -423,12 +614,507 @@ impl Project {
 
         log::debug!("Creating build file");
         {
-            let build_file = paths::try_root()
-                .ok_or(Error::NotInAProject)?
-                .join(paths::build());
-            zip_create_from_directory(&build_file, &prebuild)?; // TODO: change prebuild to bundled files
+            let build_file = self.artifact(&root, None)?;
+            let output = root.join(&self.config.build.output);
+            fs::create_dir_all(&output).map_err(BuildError::io(&output))?;
+            zip_create_from_directory(&build_file, &root.join(paths::cache_bundles()))
+                .map_err(BuildError::zip(&build_file))?;
+            report.addon = Some(build_file);
+        }
+
+        cache.inputs = inputs;
+        if let Err(e) = cache.save(&root) {
+            log::error!("Failed to save build cache: {}", e);
+        }
+
+        log::debug!("Delete prebuild directory in {}", &prebuild.display());
+        fs::remove_dir_all(&prebuild).map_err(BuildError::io(&prebuild))?;
+
+        Ok(())
+    }
+
+    /// Copies the source of `pack` to `dest` and adds the generated files like the manifest to it.
+    fn prepare_pack(&self, pack: Pack, dest: &Path, log: &mut PackLog) -> PackResult<()> {
+        log.debug(format!("Copy source to {}", dest.display()));
+        let src = pack.path_src().ok_or(BuildError::NotInAProject)?;
+        let filter = self.filter(&paths::root(), pack)?;
+        let excluded = filter.copy(dest)?;
+        if excluded > 0 {
+            log.debug(format!("Excluded {} files", excluded));
+        }
+
+        log.debug("Adding fingerprint");
+        if let Err(e) = fs::write(dest.join(paths::FINGERPRINT), self.id) {
+            log.error(format!("Failed to add fingerprint: {}", e));
+        };
+
+        if let (Pack::Behavior, Some(script)) = (pack, self.config.bp.script_settings()) {
+            log.debug("Compiling TypeScript files");
+            let files = filter.files().map_err(BuildError::io(&src))?;
+            let compiled = typescript::compile(
+                &script,
+                &paths::root(),
+                &src,
+                &files,
+                dest,
+                !self.config.debug,
+                log,
+            )?;
+            if compiled > 0 {
+                log.info(format!(
+                    "Compiled {} TypeScript files with {}",
+                    compiled,
+                    script.compiler.executable()
+                ));
+            }
+        }
+
+        if matches!(pack, Pack::Behavior | Pack::Resource) {
+            log.debug("Converting JSONC, JSON5, YAML and TOML files to JSON");
+            let replaced = transpile::transpile_dir(dest, self.config.debug, |path| {
+                path.strip_prefix(dest)
+                    .map(|relative| src.join(relative))
+                    .unwrap_or_else(|_| path.to_path_buf())
+            })?;
+            for path in replaced {
+                log.warn(format!(
+                    "{} is replaced by a file with the same name but a different extension",
+                    path.strip_prefix(dest).unwrap_or(&path).display()
+                ));
+            }
+        }
+
+        let generate_manifest = !match pack {
+            Pack::Behavior => self.config.bp.custom_manifest,
+            Pack::Resource => self.config.rp.custom_manifest,
+            Pack::Skin => self.config.sp.custom_manifest,
+            Pack::WorldTemplate => self.config.wt.custom_manifest,
+        };
+        if generate_manifest {
+            log.debug("Generating manifests");
+            for capability in manifest::capabilities(pack, &self.config) {
+                if !capability.supports(pack) {
+                    log.warn(format!(
+                        "Capability `{}` is not supported by the {} and will be ignored",
+                        capability, pack
+                    ));
+                    log.notify(diagnostic::Notification::InvalidCapability);
+                }
+            }
+            let mf = Manifest::build(pack, self.clone())?;
+            let p = dest.join("manifest.json");
+            let json = if self.config.pretty() {
+                serde_json::to_string_pretty(&mf)
+            } else {
+                serde_json::to_string(&mf)
+            }
+            .map_err(io::Error::from)
+            .map_err(BuildError::io(&p))?;
+            if p.try_exists().unwrap_or(false) && p.is_file() {
+                log.notify(diagnostic::Notification::RedundantManifest);
+            };
+            fs::write(&p, json).map_err(BuildError::io(&p))?;
+        }
+
+        let copy_pack_icon = !match pack {
+            Pack::Behavior => self.config.bp.custom_pack_icon,
+            Pack::Resource => self.config.rp.custom_pack_icon,
+            Pack::Skin => self.config.sp.custom_pack_icon,
+            Pack::WorldTemplate => false,
+        };
+        if copy_pack_icon {
+            log.debug("Copying pack icon");
+            let p = dest.join("pack_icon.png");
+            if p.try_exists().unwrap_or(false) && p.is_file() {
+                log.notify(diagnostic::Notification::RedundantPackIcon);
+            };
+            let icon = paths::root().join(paths::pack_icon());
+            fs::copy(&icon, &p).map_err(BuildError::io(&icon))?;
+        }
+
+        log.debug("Generating/extending language files");
+        {
+            let mut groups = self.config.localization.groups.clone();
+            let primary_language = &self.config.localization.primary_language;
+            let (name, desc) = self.config.overrides(pack);
+            let name: Localized<String> = name
+                .unwrap_or(&self.config.project.name)
+                .to_localized(primary_language);
+            for l in name.keys() {
+                groups.with_language(l.clone());
+            }
+
+            let desc: Localized<String> = desc
+                .unwrap_or(&self.config.project.description)
+                .to_localized(primary_language);
+            for l in desc.keys() {
+                groups.with_language(l.clone());
+            }
+
+            let mut translations: HashMap<String, Localized<String>> = HashMap::new();
+            if pack == Pack::Skin {
+                let project_name = self.config.project.name.to_localized(primary_language);
+                let project_name = project_name
+                    .get(primary_language)
+                    .or_else(|| project_name.values().next())
+                    .map(String::as_str)
+                    .unwrap_or_default();
+                if let Some(skin_pack) = SkinPack::load(
+                    dest,
+                    self.config.sp.serialize_name.as_deref(),
+                    &self.config.sp.skins,
+                    project_name,
+                )? {
+                    log.debug("Generating skins.json");
+                    let p = dest.join(skins::SKINS_JSON);
+                    let json = if self.config.pretty() {
+                        serde_json::to_string_pretty(&skin_pack.skins_json())
+                    } else {
+                        serde_json::to_string(&skin_pack.skins_json())
+                    }
+                    .map_err(io::Error::from)
+                    .map_err(BuildError::io(&p))?;
+                    if p.is_file() {
+                        log.warn(format!(
+                            "Overwriting {} with the skins defined in the configuration",
+                            skins::SKINS_JSON
+                        ));
+                    }
+                    fs::write(&p, json).map_err(BuildError::io(&p))?;
+                    let toml = dest.join(skins::SKINS_FILE);
+                    if toml.is_file() {
+                        fs::remove_file(&toml).map_err(BuildError::io(&toml))?;
+                    }
+                    for (key, translation) in skin_pack.translations(name.clone(), primary_language)
+                    {
+                        for l in translation.keys() {
+                            groups.with_language(l.clone());
+                        }
+                        translations.insert(key, translation);
+                    }
+                }
+            }
+            translations.insert("pack.name".to_string(), name);
+            translations.insert("pack.description".to_string(), desc);
+
+            let texts_dir = dest.join("texts");
+            {
+                let res = fs::create_dir(&texts_dir);
+                if res
+                    .as_ref()
+                    .is_err_and(|e| e.kind() != io::ErrorKind::AlreadyExists)
+                {
+                    res.map_err(BuildError::io(&texts_dir))?;
+                };
+            };
+
+            match collect_user_translations(&texts_dir) {
+                Ok(user_translations) => {
+                    for (key, translation) in user_translations {
+                        translations.insert(key, translation);
+                    }
+                }
+                Err(e) => log.error(format!(
+                    "Error while collecting user-defined translations: {}",
+                    e
+                )),
+            }
+
+            match update_language_files(
+                &texts_dir,
+                &groups,
+                &self.config.localization.primary_language,
+                translations,
+            ) {
+                Ok(_) => log.info("Updates language files"),
+                Err(e) => log.error(format!("Error while appending language files: {}", e)),
+            };
+            match generate_language_json(&texts_dir) {
+                Ok(_) => log.info("Generated languages.json"),
+                Err(e) => log.error(format!("Error while generating languages.json: {}", e)),
+            };
+        };
+
+        Ok(())
+    }
+
+    /// Returns the plugins whose filter evaluates to `true`.
+    fn plugins(&self) -> Result<Vec<&config::Plugin>, BuildError> {
+        let mut plugins = Vec::new();
+        for plugin in &self.config.plugin {
+            if let Some(when) = &plugin.when {
+                match filter::evaluate(when) {
+                    Ok(false) => {
+                        log::info!(
+                            "skipping running plugin {} because filter evaluated false",
+                            plugin.name.as_ref().unwrap_or(&"<unnamed>".to_string()) // TODO: change `<unnamed>`
+                        );
+                        continue;
+                    }
+                    Ok(true) => {}
+                    Err(e) => {
+                        return Err(BuildError::InvalidFilter {
+                            filter: when.clone(),
+                            reason: e.to_string(),
+                        });
+                    }
+                }
+            };
+            plugins.push(plugin);
         }
+        Ok(plugins)
+    }
 
+    fn run_plugins(
+        &self,
+        plugins: &[&config::Plugin],
+        prebuild: &Path,
+        report: &mut Report,
+    ) -> Result<(), BuildError> {
+        for plugin in plugins {
+            let name = plugin
+                .name
+                .clone()
+                .unwrap_or("<unnamed plugin>".to_string());
+            let plugin = ExecutablePlugin::from(*plugin);
+            let mut envs = Vec::new();
+            envs.push((
+                "ALLAY_DEBUG",
+                if self.config.debug {
+                    "1".into()
+                } else {
+                    "0".into()
+                },
+            ));
+            envs.push(("ALLAY_PROFILE", self.profile.clone().into()));
+            envs.push(("ALLAY_PREBUILD", prebuild.as_os_str().to_owned()));
+            envs.push(("ALLAY_PROJECT_ROOT", paths::root().into_os_string()));
+            envs.push(("ALLAY_VERSION", clap::crate_version!().into()));
+            envs.extend(
+                self.config
+                    .env
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value.into())),
+            );
+            let started = Instant::now();
+            let result = plugin.run(envs);
+            report.plugins.push(PluginReport::new(
+                plugin.name(),
+                result
+                    .as_ref()
+                    .ok()
+                    .and_then(|(_, output)| output.status.code()),
+                started.elapsed(),
+            ));
+            match result {
+                Ok((name, output)) => {
+                    let stdout = String::from_utf8_lossy(output.stdout.as_slice());
+                    let stderr = String::from_utf8_lossy(output.stderr.as_slice());
+                    for line in stdout.lines() {
+                        log::info!("[stdout of {}] {}", name, line);
+                    }
+                    for line in stderr.lines() {
+                        log::error!("[stderr of {}] {}", name, line);
+                    }
+                    if output.status.success() {
+                        log::info!("Plugin {} ran successfully", name);
+                    } else {
+                        log::error!("Plugin {} ran unsuccessfully", name);
+                        if plugin.panic() {
+                            return Err(BuildError::PluginFailed {
+                                name,
+                                reason: output.status.to_string(),
+                            });
+                        }
+                    }
+                }
+                Err(e) => {
+                    log::error!("Failed to run plugin {}: {}", name, e);
+                    if plugin.panic() {
+                        return Err(e);
+                    }
+                }
+            };
+        }
         Ok(())
     }
+
+    /// Returns a hash of everything besides from the pack sources that influences the build.
+    fn inputs_hash(&self, root: &Path, plugins: &[&config::Plugin]) -> Result<String, BuildError> {
+        let mut inputs = FileHashes::new();
+        inputs.insert(
+            "allay".to_string(),
+            cache::hash(clap::crate_version!().as_bytes()),
+        );
+        inputs.insert(
+            "debug".to_string(),
+            cache::hash(&[u8::from(self.config.debug)]),
+        );
+        inputs.insert("profile".to_string(), cache::hash(self.profile.as_bytes()));
+        for path in [
+            paths::config(),
+            paths::uuids(),
+            paths::pack_icon(),
+            paths::allayignore(),
+        ] {
+            let file = root.join(&path);
+            if file.is_file() {
+                inputs.insert(
+                    path.to_string_lossy().to_string(),
+                    cache::hash_file(&file).map_err(BuildError::io(&file))?,
+                );
+            }
+        }
+
+        // plugins usually reference their scripts by relative paths
+        for (i, plugin) in plugins.iter().enumerate() {
+            inputs.insert(format!("plugin/{}", i), cache::hash(plugin.run.as_bytes()));
+            let args = match &plugin.args {
+                Some(config::PluginArgs::Args(args)) => args.as_slice(),
+                _ => &[],
+            };
+            for arg in std::iter::once(&plugin.run).chain(args) {
+                let file = root.join(arg);
+                if file.is_file() {
+                    inputs.insert(
+                        format!("plugin/{}/{}", i, arg),
+                        cache::hash_file(&file).map_err(BuildError::io(&file))?,
+                    );
+                }
+            }
+        }
+
+        for dir in &self.config.build.extra_watch_dirs {
+            let dir_path = root.join(dir);
+            for (path, hash) in cache::hash_dir(&dir_path).map_err(BuildError::io(&dir_path))? {
+                inputs.insert(format!("{}/{}", dir.display(), path), hash);
+            }
+        }
+
+        Ok(cache::digest(&inputs))
+    }
+}
+
+/// Returns the name of the profile matching the `debug` field of `config`.
+fn default_profile(config: &Config) -> &'static str {
+    if config.debug {
+        config::DEV_PROFILE
+    } else {
+        config::RELEASE_PROFILE
+    }
+}
+
+type PackResult<T> = Result<T, BuildError>;
+
+/// Runs `f` for each pack in its own thread.
+///
+/// The log records of each pack are emitted in the order of `packs` once all threads finished so that they
+/// stay grouped. When any pack fails, the error of the first failed pack is returned and the errors of the
+/// other failed packs are logged.
+fn in_parallel<T, F>(
+    packs: &[Pack],
+    report: &mut Report,
+    f: F,
+) -> Result<Vec<(Pack, T)>, BuildError>
+where
+    T: Send,
+    F: Fn(Pack, &mut PackLog) -> PackResult<T> + Sync,
+{
+    let results: Vec<(Pack, PackResult<T>, PackLog)> = thread::scope(|scope| {
+        let handles: Vec<_> = packs
+            .iter()
+            .map(|&pack| {
+                let f = &f;
+                scope.spawn(move || {
+                    let mut log = PackLog::new(pack);
+                    let result = f(pack, &mut log);
+                    (pack, result, log)
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("pack thread panicked"))
+            .collect()
+    });
+
+    let mut values = Vec::new();
+    let mut failure = None;
+    for (pack, result, log) in results {
+        log.flush(report);
+        match result {
+            Ok(value) => values.push((pack, value)),
+            // the first error is logged by the caller
+            Err(e) if failure.is_none() => failure = Some(e.in_pack(pack)),
+            Err(e) => {
+                let e = e.in_pack(pack);
+                e.log();
+                report.record(Some(pack), e.notification());
+            }
+        }
+    }
+    match failure {
+        Some(e) => Err(e),
+        None => Ok(values),
+    }
+}
+
+/// Buffers log records of a pack so that they are not interleaved with the records of other packs.
+pub(crate) struct PackLog {
+    pack: Pack,
+    records: Vec<(log::Level, String)>,
+    notifications: Vec<diagnostic::Notification>,
+}
+
+impl PackLog {
+    fn new(pack: Pack) -> Self {
+        Self {
+            pack,
+            records: Vec::new(),
+            notifications: Vec::new(),
+        }
+    }
+
+    fn log(&mut self, level: log::Level, message: impl fmt::Display) {
+        self.records.push((level, message.to_string()));
+    }
+
+    pub(crate) fn debug(&mut self, message: impl fmt::Display) {
+        self.log(log::Level::Debug, message);
+    }
+
+    pub(crate) fn info(&mut self, message: impl fmt::Display) {
+        self.log(log::Level::Info, message);
+    }
+
+    pub(crate) fn warn(&mut self, message: impl fmt::Display) {
+        self.log(log::Level::Warn, message);
+    }
+
+    pub(crate) fn error(&mut self, message: impl fmt::Display) {
+        self.log(log::Level::Error, message);
+    }
+
+    /// Logs `notification` and records it for the build report.
+    fn notify(&mut self, notification: diagnostic::Notification) {
+        self.log(
+            match notification.kind() {
+                diagnostic::Kind::Error => log::Level::Error,
+                diagnostic::Kind::Warning => log::Level::Warn,
+            },
+            notification,
+        );
+        self.notifications.push(notification);
+    }
+
+    /// Emits all buffered records prefixed with the short name of the pack and adds the notifications to
+    /// `report`.
+    fn flush(self, report: &mut Report) {
+        for (level, message) in self.records {
+            log::log!(level, "[{}] {}", self.pack.short_name(), message);
+        }
+        for notification in self.notifications {
+            report.record(Some(self.pack), notification);
+        }
+    }
 }
@@ -0,0 +1,197 @@
+//! Machine-readable summary of a build.
+//!
+//! The report is printed by `allay build --report json` and is intended to be consumed by tools like CI
+//! pipelines.
+
+use crate::diagnostic::{Diagnostic, Kind, Notification};
+use crate::manifest::{Manifest, Version};
+use crate::{BuildError, Pack};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    /// The version of Allay used for the build.
+    pub allay_version: String,
+
+    /// Whether the project was built in debug mode.
+    pub debug: bool,
+
+    /// The name of the build profile.
+    pub profile: String,
+
+    /// Whether the build succeeded.
+    pub success: bool,
+
+    /// The error which aborted the build if it failed.
+    pub error: Option<ErrorReport>,
+
+    /// The path of the built add-on.
+    pub addon: Option<PathBuf>,
+
+    /// How long the build took in milliseconds.
+    pub duration_ms: u128,
+
+    /// The packs that were built.
+    pub packs: Vec<PackReport>,
+
+    /// The plugins that were run in the order they were run.
+    pub plugins: Vec<PluginReport>,
+
+    /// Every notification raised during the build.
+    pub notifications: Vec<NotificationReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PackReport {
+    /// The short name of the pack (e.g. `BP`).
+    pub pack: String,
+
+    /// The path of the bundle (e.g. `.mcpack` file) of the pack in the build cache which is overwritten by
+    /// the next build. The `pack` command writes the bundles to the output directory.
+    pub cache_path: PathBuf,
+
+    /// The size of the bundle in bytes.
+    pub size: u64,
+
+    /// Whether the bundle of the previous build was reused.
+    pub cached: bool,
+
+    /// The generated manifest or [`None`] if the pack uses a custom manifest.
+    pub manifest: Option<ManifestReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ManifestReport {
+    /// The UUID in the header section of the manifest.
+    pub header: String,
+
+    /// The UUIDs of the modules of the manifest.
+    pub modules: Vec<String>,
+
+    /// The version of the pack.
+    pub version: Version,
+}
+
+impl From<&Manifest> for ManifestReport {
+    fn from(value: &Manifest) -> Self {
+        Self {
+            header: value.header.uuid.clone(),
+            modules: value
+                .modules
+                .iter()
+                .flatten()
+                .map(|module| module.uuid.clone())
+                .collect(),
+            version: value.header.version.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PluginReport {
+    /// The name of the plugin if any.
+    pub name: Option<String>,
+
+    /// Whether the plugin exited successfully.
+    pub success: bool,
+
+    /// The exit code of the plugin or [`None`] if it could not be started or was terminated by a signal.
+    pub exit_code: Option<i32>,
+
+    /// How long the plugin ran in milliseconds.
+    pub duration_ms: u128,
+}
+
+impl PluginReport {
+    pub fn new(name: Option<String>, exit_code: Option<i32>, duration: Duration) -> Self {
+        Self {
+            name,
+            success: exit_code == Some(0),
+            exit_code,
+            duration_ms: duration.as_millis(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorReport {
+    /// The ID of the error (e.g. `E009`) which can be passed to `allay explain`.
+    pub id: String,
+
+    /// The error message.
+    pub message: String,
+
+    /// The short name of the pack that failed to build if any.
+    pub pack: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationReport {
+    #[serde(skip)]
+    pub code: u8,
+
+    /// The ID of the notification (e.g. `W001`) which can be passed to `allay explain`.
+    pub id: String,
+
+    /// Whether the notification is an error or a warning.
+    pub kind: String,
+
+    /// A brief description of the notification.
+    pub message: String,
+
+    /// The short name of the pack the notification relates to if any.
+    pub pack: Option<String>,
+}
+
+impl Report {
+    pub fn new(debug: bool, profile: &str) -> Self {
+        Self {
+            allay_version: clap::crate_version!().to_string(),
+            debug,
+            profile: profile.to_string(),
+            success: true,
+            ..Default::default()
+        }
+    }
+
+    /// Marks the build as failed because of `error` and adds the notification of the error.
+    pub fn fail(&mut self, error: &BuildError) {
+        let (pack, error) = match error {
+            BuildError::Pack { pack, source } => (Some(*pack), source.as_ref()),
+            _ => (None, error),
+        };
+        self.success = false;
+        self.record(pack, error.notification());
+        self.error = Some(ErrorReport {
+            id: error.notification().id(),
+            message: error.to_string(),
+            pack: pack.map(|p| p.short_name().to_string()),
+        });
+    }
+
+    /// Logs `notification` and adds it to the report.
+    pub fn notify(&mut self, pack: Option<Pack>, notification: Notification) {
+        match notification.kind() {
+            Kind::Error => log::error!("{}", notification),
+            Kind::Warning => log::warn!("{}", notification),
+        };
+        self.record(pack, notification);
+    }
+
+    /// Adds `notification` to the report without logging it.
+    pub fn record(&mut self, pack: Option<Pack>, notification: Notification) {
+        self.notifications.push(NotificationReport {
+            code: notification.code(),
+            id: notification.id(),
+            kind: match notification.kind() {
+                Kind::Error => "error",
+                Kind::Warning => "warning",
+            }
+            .to_string(),
+            message: notification.brief_description().to_string(),
+            pack: pack.map(|p| p.short_name().to_string()),
+        });
+    }
+}
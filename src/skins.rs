@@ -0,0 +1,251 @@
+//! `skins.json` generator for skin packs.
+//!
+//! Skins are defined in the `[[SP.skins]]` sections of the configuration file or in the `skins.toml` file
+//! located in the skin pack. Both are combined.
+//!
+//! # Examples
+//!
+//! Below is an example of how a `skins.toml` file could look like.
+//!
+//! ```toml
+//! serialize-name = "MyAwesomeSkins"
+//!
+//! [[skins]]
+//! id = "steve"
+//! name = { en-us = "Steve", de-de = "Steffen" }
+//! texture = "steve.png"
+//!
+//! [[skins]]
+//! id = "alex"
+//! name = "Alex"
+//! texture = "alex.png"
+//! geometry = "geometry.humanoid.customSlim"
+//! ```
+//!
+//! # References
+//!
+//! - <https://learn.microsoft.com/en-us/minecraft/creator/documents/packagingaskinpack?view=minecraft-bedrock-stable>
+
+use crate::localization::{Language, Localized, OptionallyLocalized};
+use crate::{paths, BuildError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the file within the skin pack which may define skins besides from the configuration file.
+pub const SKINS_FILE: &str = "skins.toml";
+
+/// Name of the generated file.
+pub const SKINS_JSON: &str = "skins.json";
+
+fn default_geometry() -> String {
+    String::from("geometry.humanoid.custom")
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+pub struct Skin {
+    /// The identifier of the skin used for the language keys (e.g. `steve`).
+    pub id: String,
+
+    /// The displayed name of the skin.
+    pub name: OptionallyLocalized<String>,
+
+    /// The path of the texture relative to the skin pack (e.g. `steve.png`).
+    pub texture: String,
+
+    /// The geometry of the skin which is `geometry.humanoid.custom` for the classic model and
+    /// `geometry.humanoid.customSlim` for the slim model.
+    #[serde(default = "default_geometry")]
+    pub geometry: String,
+
+    /// Whether the skin is free or paid.
+    #[serde(rename = "type")]
+    #[serde(default)]
+    pub kind: SkinType,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+pub enum SkinType {
+    #[default]
+    Free,
+    Paid,
+}
+
+/// The content of the `skins.toml` file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+struct SkinsFile {
+    serialize_name: Option<String>,
+
+    #[serde(default)]
+    skins: Vec<Skin>,
+}
+
+/// The `skins.json` file of a skin pack.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SkinsJson {
+    pub skins: Vec<SkinEntry>,
+    pub serialize_name: String,
+    pub localization_name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SkinEntry {
+    pub localization_name: String,
+    pub geometry: String,
+    pub texture: String,
+    #[serde(rename = "type")]
+    pub kind: SkinType,
+}
+
+/// The skins of a skin pack.
+#[derive(Debug)]
+pub struct SkinPack {
+    /// The name used to identify the skin pack in `skins.json` and the language keys.
+    pub serialize_name: String,
+
+    pub skins: Vec<Skin>,
+}
+
+impl SkinPack {
+    /// Combines the skins defined in the configuration with those in the `skins.toml` file in `dir`.
+    ///
+    /// `serialize_name` is used unless the `skins.toml` file defines one. Returns [`None`] when no skins are
+    /// defined.
+    pub fn load(
+        dir: &Path,
+        serialize_name: Option<&str>,
+        skins: &[Skin],
+        project_name: &str,
+    ) -> Result<Option<Self>, BuildError> {
+        let path = dir.join(SKINS_FILE);
+        // errors refer to the file in the source directory instead of its copy
+        let source = paths::src_sp().join(SKINS_FILE);
+        let file = if path.is_file() {
+            let content = fs::read_to_string(&path).map_err(BuildError::io(&path))?;
+            toml::from_str::<SkinsFile>(&content)
+                .map_err(|e| BuildError::config(&source, &content, e.span(), e.message()))?
+        } else {
+            SkinsFile::default()
+        };
+
+        let mut all: Vec<Skin> = Vec::new();
+        let mut defined_in: Vec<PathBuf> = Vec::new();
+        let skins = skins
+            .iter()
+            .cloned()
+            .map(|skin| (skin, paths::config()))
+            .chain(file.skins.into_iter().map(|skin| (skin, source.clone())));
+        for (skin, path) in skins {
+            if let Some(i) = all.iter().position(|s| s.id == skin.id) {
+                let message = if defined_in[i] == path {
+                    format!("skin `{}` is defined more than once", skin.id)
+                } else {
+                    format!(
+                        "skin `{}` is already defined in {}",
+                        skin.id,
+                        defined_in[i].display()
+                    )
+                };
+                // a skin of the configuration can only be repeated within the configuration
+                if path != source {
+                    return Err(BuildError::invalid_config(message));
+                }
+                return Err(BuildError::InvalidConfig {
+                    path,
+                    line: None,
+                    column: None,
+                    message,
+                });
+            }
+            all.push(skin);
+            defined_in.push(path);
+        }
+        if all.is_empty() {
+            return Ok(None);
+        }
+
+        let serialize_name = match file.serialize_name.as_deref().or(serialize_name) {
+            Some(name) => name.to_string(),
+            None => project_name
+                .chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect(),
+        };
+        if serialize_name.is_empty() {
+            return Err(BuildError::invalid_config(
+                "cannot derive a serialize name from the project name; set `SP.serialize-name`",
+            ));
+        }
+
+        Ok(Some(Self {
+            serialize_name,
+            skins: all,
+        }))
+    }
+
+    pub fn skins_json(&self) -> SkinsJson {
+        SkinsJson {
+            skins: self
+                .skins
+                .iter()
+                .map(|skin| SkinEntry {
+                    localization_name: skin.id.clone(),
+                    geometry: skin.geometry.clone(),
+                    texture: skin.texture.clone(),
+                    kind: skin.kind,
+                })
+                .collect(),
+            serialize_name: self.serialize_name.clone(),
+            localization_name: self.serialize_name.clone(),
+        }
+    }
+
+    /// Returns the translations of the skin pack's name (`skinpack.<pack>`) and of each skin
+    /// (`skin.<pack>.<skin>`).
+    ///
+    /// `language` is used for unlocalized names.
+    pub fn translations(
+        &self,
+        name: Localized<String>,
+        language: &Language,
+    ) -> HashMap<String, Localized<String>> {
+        let mut translations = HashMap::new();
+        translations.insert(format!("skinpack.{}", self.serialize_name), name);
+        for skin in &self.skins {
+            translations.insert(
+                format!("skin.{}.{}", self.serialize_name, skin.id),
+                skin.name.to_localized(language),
+            );
+        }
+        translations
+    }
+}
+
+/// Ensures that the texture of every skin in the `skins.json` file of the skin pack located at `dir` exists.
+///
+/// Skin packs without a valid `skins.json` file are not checked.
+pub fn check_textures(dir: &Path) -> Result<(), BuildError> {
+    let Ok(content) = fs::read_to_string(dir.join(SKINS_JSON)) else {
+        return Ok(());
+    };
+    let Ok(skins_json) = serde_json::from_str::<SkinsJson>(&content) else {
+        return Ok(());
+    };
+    for skin in skins_json.skins {
+        if !dir.join(&skin.texture).is_file() {
+            return Err(BuildError::MissingSkinTexture {
+                skin: skin.localization_name,
+                texture: skin.texture,
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,112 @@
+//! Conversion of source files written in other formats than JSON.
+//!
+//! Files with the extension `jsonc` (JSON with comments), `json5` ([JSON5][]), `yaml`, `yml` or `toml` in
+//! behavior packs and resource packs are written as strict JSON files with the `json` extension at the same
+//! relative path before plugins run. The order of keys is preserved.
+//!
+//! [JSON5]: https://json5.org/
+
+use crate::minify;
+use crate::BuildError;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File extensions of the files that are transpiled to JSON.
+pub const EXTENSIONS: [&str; 5] = ["jsonc", "json5", "yaml", "yml", "toml"];
+
+/// Parses the JSON5 document `content` of the file at `path`.
+pub fn parse_json5(path: &Path, content: &str) -> Result<serde_json::Value, BuildError> {
+    json5::from_str(content).map_err(|e| {
+        let json5::Error::Message { msg, location } = e;
+        BuildError::InvalidJson {
+            path: path.to_path_buf(),
+            line: location.as_ref().map(|l| l.line),
+            column: location.as_ref().map(|l| l.column),
+            // the parser includes an excerpt of the file; only keep what was expected
+            message: msg
+                .lines()
+                .last()
+                .map(|line| line.trim_start_matches([' ', '='].as_slice()))
+                .unwrap_or_default()
+                .to_string(),
+        }
+    })
+}
+
+/// Parses the YAML document `content` of the file at `path`.
+pub fn parse_yaml(path: &Path, content: &str) -> Result<serde_json::Value, BuildError> {
+    serde_norway::from_str(content).map_err(|e| {
+        let location = e.location();
+        let message = match &location {
+            Some(l) => minify::strip_location(&e.to_string(), l.line(), l.column()),
+            None => e.to_string(),
+        };
+        BuildError::InvalidJson {
+            path: path.to_path_buf(),
+            line: location.as_ref().map(|l| l.line()),
+            column: location.as_ref().map(|l| l.column()),
+            message,
+        }
+    })
+}
+
+/// Parses the TOML document `content` of the file at `path`.
+pub fn parse_toml(path: &Path, content: &str) -> Result<serde_json::Value, BuildError> {
+    toml::from_str(content).map_err(|e| BuildError::json(path, content, e.span(), e.message()))
+}
+
+/// Parses `content` according to the extension of `path` and returns it as JSON.
+pub fn to_json(path: &Path, content: &str, pretty: bool) -> Result<String, BuildError> {
+    let value = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json5") => parse_json5(path, content)?,
+        Some("yaml" | "yml") => parse_yaml(path, content)?,
+        Some("toml") => parse_toml(path, content)?,
+        _ => minify::parse(path, content)?,
+    };
+    let json = if pretty {
+        serde_json::to_string_pretty(&value)
+    } else {
+        serde_json::to_string(&value)
+    };
+    Ok(json.expect("JSON values can always be serialized"))
+}
+
+/// Transpiles every file in `dir` with one of the [`EXTENSIONS`] to a JSON file and removes the original.
+///
+/// `display_path` maps the path of a file to the one shown in errors. Returns the paths of the written JSON
+/// files that replaced an existing file.
+pub fn transpile_dir<F>(
+    dir: &Path,
+    pretty: bool,
+    display_path: F,
+) -> Result<Vec<PathBuf>, BuildError>
+where
+    F: Fn(&Path) -> PathBuf,
+{
+    let mut sources = Vec::new();
+    for entry in walkdir::WalkDir::new(dir) {
+        let entry = entry.map_err(BuildError::walk(dir))?;
+        let path = entry.path();
+        let is_source = entry.file_type().is_file()
+            && path
+                .extension()
+                .is_some_and(|ext| EXTENSIONS.iter().any(|e| ext == *e));
+        if is_source {
+            sources.push(path.to_path_buf());
+        }
+    }
+
+    let mut replaced = Vec::new();
+    for path in &sources {
+        let path = path.as_path();
+        let content = fs::read_to_string(path).map_err(BuildError::io(path))?;
+        let json = to_json(&display_path(path), &content, pretty)?;
+        let dest = path.with_extension("json");
+        if dest.exists() {
+            replaced.push(dest.clone());
+        }
+        fs::write(&dest, json).map_err(BuildError::io(&dest))?;
+        fs::remove_file(path).map_err(BuildError::io(path))?;
+    }
+    Ok(replaced)
+}
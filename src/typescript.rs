@@ -0,0 +1,143 @@
+//! Compilation of the TypeScript files of behavior packs.
+//!
+//! When the behavior pack has a `[BP.script]` section and contains TypeScript files, they are compiled into
+//! the `scripts` directory of the prebuild directory by either [esbuild][] or the [TypeScript compiler][tsc]
+//! and removed from the pack afterwards. The sources are compiled from `src/BP` so that imports of packages
+//! installed in the project's `node_modules` directory can be resolved. Files excluded from the pack are not
+//! passed to the compiler.
+//!
+//! [esbuild]: https://esbuild.github.io/
+//! [tsc]: https://www.typescriptlang.org/docs/handbook/compiler-options.html
+
+use crate::config::{Compiler, Script};
+use crate::project::PackLog;
+use crate::BuildError;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// File extension of TypeScript files.
+pub const TYPESCRIPT_FILE_EXTENSION: &str = "ts";
+
+/// The ECMAScript version the scripts are compiled to.
+const TARGET: &str = "es2020";
+
+/// Returns whether `path` is a TypeScript file.
+pub fn is_source(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == TYPESCRIPT_FILE_EXTENSION)
+}
+
+/// Returns the TypeScript files within `dir`.
+fn sources(dir: &Path) -> Result<Vec<PathBuf>, BuildError> {
+    let mut sources = Vec::new();
+    for entry in walkdir::WalkDir::new(dir) {
+        let entry = entry.map_err(BuildError::walk(dir))?;
+        if entry.file_type().is_file() && is_source(entry.path()) {
+            sources.push(entry.path().to_path_buf());
+        }
+    }
+    Ok(sources)
+}
+
+/// Returns the executable of the compiler for the project located at `root`.
+fn executable(script: &Script, root: &Path) -> PathBuf {
+    if let Some(path) = &script.compiler_path {
+        return root.join(path);
+    }
+    let name = script.compiler.executable();
+    let local = root
+        .join("node_modules")
+        .join(".bin")
+        .join(if cfg!(windows) {
+            format!("{}.cmd", name)
+        } else {
+            name.to_string()
+        });
+    if local.is_file() {
+        local
+    } else {
+        PathBuf::from(name)
+    }
+}
+
+/// Compiles the TypeScript files among `files` of `src` (e.g. `src/BP`) of the project located at `root` into
+/// `dest` and removes the TypeScript files from `dest`. The command and its output are logged to `log`.
+///
+/// `files` are the files which are part of the pack. Returns the number of compiled files which is zero
+/// when there are no TypeScript files.
+pub fn compile(
+    script: &Script,
+    root: &Path,
+    src: &Path,
+    files: &[PathBuf],
+    dest: &Path,
+    release: bool,
+    log: &mut PackLog,
+) -> Result<usize, BuildError> {
+    let files: Vec<&PathBuf> = files.iter().filter(|path| is_source(path)).collect();
+    if files.is_empty() {
+        return Ok(0);
+    }
+
+    let program = executable(script, root);
+    let mut cmd = Command::new(&program);
+    cmd.current_dir(root);
+    match script.compiler {
+        Compiler::Esbuild => {
+            cmd.arg(src.join(script.source_entry()))
+                .arg("--bundle")
+                .arg("--format=esm")
+                .arg(format!("--target={}", TARGET))
+                .arg("--external:@minecraft/*")
+                .arg(format!("--outfile={}", dest.join(script.entry()).display()));
+            if release {
+                cmd.arg("--minify");
+            }
+        }
+        Compiler::Tsc => {
+            if root.join("tsconfig.json").is_file() {
+                cmd.arg("--project").arg(root.join("tsconfig.json"));
+            } else {
+                cmd.arg("--module")
+                    .arg(TARGET)
+                    .arg("--target")
+                    .arg(TARGET)
+                    .arg("--moduleResolution")
+                    .arg("node")
+                    .arg("--skipLibCheck")
+                    .args(&files);
+            }
+            cmd.arg("--rootDir").arg(src).arg("--outDir").arg(dest);
+        }
+    }
+
+    let name = script.compiler.executable().to_string();
+    log.debug(format!("Running {:?}", cmd));
+    let output = cmd.output().map_err(|e| BuildError::CompilerFailed {
+        compiler: name.clone(),
+        reason: format!("cannot run `{}`: {}", program.display(), e),
+    })?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for line in stdout.lines().chain(stderr.lines()) {
+        log.debug(format!("[{}] {}", name, line));
+    }
+    if !output.status.success() {
+        // `tsc` reports errors to the standard output
+        let reason = match (stdout.trim(), stderr.trim()) {
+            (stdout, "") if !stdout.is_empty() => stdout.to_string(),
+            ("", "") => format!("exited with {}", output.status),
+            (_, stderr) => stderr.to_string(),
+        };
+        return Err(BuildError::CompilerFailed {
+            compiler: name,
+            reason,
+        });
+    }
+
+    for source in sources(dest)? {
+        fs::remove_file(&source).map_err(BuildError::io(&source))?;
+    }
+    Ok(files.len())
+}
@@ -12,6 +12,9 @@
 //! module = "500c2b6a-6c8b-4423-9236-65e48ff76ab0"
 //! dependencies = ["eb7d9bca-ad5d-4160-a86d-65ce8122daa4", "d64870ce-ebd6-453c-8082-0273fdb9a912"]
 //!
+//! [BP.modules]
+//! script = "0b0b3c4e-3f5e-4b7a-a0a4-4f2ad1e0b1d6"
+//!
 //! [RP]
 //!
 //! [SP]
@@ -19,9 +22,11 @@
 //! [WT]
 //! ```
 
+use crate::manifest::ModuleType;
 use crate::Pack;
 use prettytable::{row, Table};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -29,6 +34,10 @@ pub struct Data {
     pub header: Option<libuuid::Uuid>,
     pub module: Option<libuuid::Uuid>,
     pub deps: Vec<libuuid::Uuid>,
+
+    /// The UUIDs of modules besides from the primary module of the pack keyed by their type (e.g. `script`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub modules: BTreeMap<String, libuuid::Uuid>,
 }
 
 impl Data {
@@ -40,6 +49,7 @@ impl Data {
             header: Some(libuuid::Uuid::new_v4()),
             module: Some(libuuid::Uuid::new_v4()),
             deps: Vec::new(),
+            modules: BTreeMap::new(),
         }
     }
 
@@ -64,6 +74,39 @@ impl Data {
         });
         self
     }
+
+    /// Returns the UUID of the module of type `kind` of `pack`.
+    ///
+    /// The UUID of the primary module of the pack (e.g. `resources` for resource packs) is stored in
+    /// `module` whereas the UUIDs of all other modules are stored in `modules`.
+    pub fn module_of(&self, pack: Pack, kind: ModuleType) -> Option<libuuid::Uuid> {
+        if kind == ModuleType::primary(pack) {
+            self.module
+        } else {
+            self.modules.get(kind.key()).copied()
+        }
+    }
+
+    /// Updates the UUID of the module of type `kind` of `pack` with `uuid` or generates a new one if `uuid`
+    /// is [`None`].
+    pub fn update_module_of(
+        &mut self,
+        pack: Pack,
+        kind: ModuleType,
+        uuid: Option<libuuid::Uuid>,
+    ) -> &mut Self {
+        if kind == ModuleType::primary(pack) {
+            return self.update_module(uuid);
+        }
+        self.modules.insert(
+            kind.key().to_string(),
+            match uuid {
+                Some(x) => x,
+                None => libuuid::Uuid::new_v4(),
+            },
+        );
+        self
+    }
 }
 
 #[derive(Clone, Default, Debug, Deserialize, Serialize)]
@@ -78,7 +121,7 @@ pub struct Uuids {
 impl From<Uuids> for Table {
     fn from(val: Uuids) -> Self {
         let mut table = Table::new();
-        table.add_row(row![b => "", "Header", "Module", "Dependencies"]);
+        table.add_row(row![b => "", "Header", "Module", "Other Modules", "Dependencies"]);
         for (name, pack) in [
             ("BP", val.bp),
             ("RP", val.rp),
@@ -89,6 +132,11 @@ impl From<Uuids> for Table {
                 name,
                 pack.header.map(|u| u.to_string()).unwrap_or_default(),
                 pack.module.map(|u| u.to_string()).unwrap_or_default(),
+                pack.modules
+                    .iter()
+                    .map(|(key, u)| format!("{}: {}", key, u))
+                    .collect::<Vec<_>>()
+                    .join(", "),
                 pack.deps
                     .iter()
                     .map(|u| u.to_string())
@@ -120,6 +168,15 @@ impl Uuids {
         }
     }
 
+    pub fn of_mut(&mut self, pack: &Pack) -> &mut Data {
+        match pack {
+            Pack::Behavior => &mut self.bp,
+            Pack::Resource => &mut self.rp,
+            Pack::Skin => &mut self.sp,
+            Pack::WorldTemplate => &mut self.wt,
+        }
+    }
+
     /// Loads UUIDs from TOML string.
     pub fn from_str(s: &str) -> Result<Self, toml::de::Error> {
         toml::from_str(s)